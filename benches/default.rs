@@ -1,11 +1,12 @@
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use crypto_bigint::{Uint, Wrapping};
 use pfhe::{
     bigint::{BigIntTrait, WrappingCryptoBigInt},
     crypto_parameters::{EncryptionScheme, PrivateKeySchemeCryptographicParameters},
-    hensel_code::HenselCode,
+    hensel_code::{new_hensel_code, new_hensel_codes, HenselCode},
     rational::Rational,
 };
-use std::time::Instant;
+use std::{convert::TryFrom, time::Instant};
 
 pub fn criterion_benchmark(c: &mut Criterion) {
     type T = WrappingCryptoBigInt;
@@ -38,5 +39,97 @@ pub fn criterion_benchmark(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, criterion_benchmark);
+fn bench_mul_at_size<const L: usize>(c: &mut Criterion) {
+    let max = WrappingCryptoBigInt::<L>(Wrapping(Uint::<L>::MAX));
+    let (a, b) = (
+        WrappingCryptoBigInt::<L>::random_mod(&max),
+        WrappingCryptoBigInt::<L>::random_mod(&max),
+    );
+
+    let mut group = c.benchmark_group("multiply big ints");
+    group.bench_with_input(BenchmarkId::new("mul", L), &(&a, &b), |bencher, (a, b)| {
+        bencher.iter(|| black_box(a).mul(black_box(b)))
+    });
+    group.bench_with_input(
+        BenchmarkId::new("mul_tuned", L),
+        &(&a, &b),
+        |bencher, (a, b)| bencher.iter(|| black_box(a).mul_tuned(black_box(b))),
+    );
+    group.finish();
+}
+
+pub fn mul_benchmark(c: &mut Criterion) {
+    bench_mul_at_size::<16>(c);
+    bench_mul_at_size::<64>(c);
+    bench_mul_at_size::<128>(c);
+    bench_mul_at_size::<256>(c);
+}
+
+pub fn pow_benchmark(c: &mut Criterion) {
+    type T = WrappingCryptoBigInt<8>;
+    let modulus = T::from_u128((1 << 127) - 1);
+    let hc = HenselCode::random(&modulus);
+    let exponent = T::random_mod(&modulus);
+
+    let mut group = c.benchmark_group("raise hensel codes to a power");
+    group.bench_function("pow", |b| b.iter(|| hc.pow(black_box(&exponent))));
+    for window in [2, 4, 6] {
+        group.bench_with_input(BenchmarkId::new("pow_naf", window), &window, |b, window| {
+            b.iter(|| hc.pow_naf(black_box(&exponent), *window))
+        });
+    }
+    group.finish();
+}
+
+pub fn encrypt_int_benchmark(c: &mut Criterion) {
+    type T = WrappingCryptoBigInt<4>;
+    let crypto_params =
+        PrivateKeySchemeCryptographicParameters::<T>::try_from("7919,37,41,5897,7759").unwrap();
+    let g = crypto_params.prime_product();
+    let n = T::from_u128(43);
+    let r = Rational::from(n.clone());
+
+    let mut group = c.benchmark_group("encrypt integers");
+    group.bench_function("encode as rational", |b| {
+        b.iter(|| HenselCode::from(black_box((g, &r))))
+    });
+    group.bench_function("encode as residue", |b| {
+        b.iter(|| new_hensel_code(black_box(g), black_box(&n)))
+    });
+    group.bench_function("encrypt_int", |b| {
+        b.iter(|| crypto_params.encrypt_int(black_box(&n)))
+    });
+    group.finish();
+}
+
+pub fn residue_params_benchmark(c: &mut Criterion) {
+    type T = WrappingCryptoBigInt<4>;
+    let crypto_params =
+        PrivateKeySchemeCryptographicParameters::<T>::try_from("7919,37,41,5897,7759").unwrap();
+    let g = crypto_params.prime_product();
+    let ns: Vec<T> = (0..64).map(|_| T::random_mod(&g.mul(g))).collect();
+
+    let mut group = c.benchmark_group("reduce residues modulo g");
+    group.bench_function("new_hensel_code", |b| {
+        b.iter(|| {
+            black_box(&ns)
+                .iter()
+                .map(|n| new_hensel_code(g, n))
+                .collect::<Vec<_>>()
+        })
+    });
+    group.bench_function("new_hensel_codes (shared params)", |b| {
+        b.iter(|| new_hensel_codes(g, black_box(&ns)))
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    criterion_benchmark,
+    mul_benchmark,
+    pow_benchmark,
+    encrypt_int_benchmark,
+    residue_params_benchmark
+);
 criterion_main!(benches);
@@ -0,0 +1,36 @@
+use num_bigint_dig::BigInt;
+use pfhe::{
+    bigint::BigIntTrait,
+    crypto_parameters::{EncryptionScheme, PublicKeySchemeCryptographicParameters},
+    rational::Rational,
+};
+use std::time::Instant;
+
+fn main() {
+    type T = BigInt;
+
+    println!("generating crypto params ...");
+    let now = Instant::now();
+    // secure parameter would be lambda = 512
+    let crypto_params =
+        PublicKeySchemeCryptographicParameters::<T>::new_from_number_operations(128, 1);
+    println!("crypto params generated in {:.2?}", now.elapsed());
+
+    let message1 = Rational::<T> {
+        num: T::from_u128(7),
+        denom: T::from_u128(3),
+    };
+    let message2 = Rational::<T> {
+        num: T::from_u128(16),
+        denom: T::from_u128(5),
+    };
+    println!("message 1: {message1}");
+    println!("message 2: {message2}");
+
+    let ciphertext1 = crypto_params.encrypt(message1.clone());
+    let ciphertext2 = crypto_params.encrypt(message2.clone());
+    let ciphertext_sum = &ciphertext1 + &ciphertext2;
+
+    let sum = crypto_params.decrypt(ciphertext_sum);
+    println!("decrypted sum: {sum} (expected {})", message1 + message2);
+}
@@ -1,25 +1,36 @@
 extern crate num_bigint_dig;
 extern crate rand;
 
-use num_bigint_dig::{BigInt, RandPrime, RandomBits};
-use rand::{thread_rng, Rng};
+use num_bigint_dig::{BigInt, RandBigInt, RandPrime};
+use rand::{thread_rng, CryptoRng, RngCore};
 
+#[cfg(feature = "num-traits")]
+use crate::impl_big_int_trait_op;
 use crate::shared::DEFAULT_LIMBS;
 
 use crypto_bigint::{
+    modular::runtime_mod::{DynResidue, DynResidueParams},
     rand_core::OsRng,
     subtle::{Choice, ConditionallySelectable, ConstantTimeEq, ConstantTimeLess},
-    Checked, NonZero, RandomMod, Uint, Wrapping, Zero,
+    Checked, CheckedAdd, Integer, Limb, NonZero, RandomMod, Uint, WideWord, Word, Wrapping, Zero,
 };
 
-use crypto_primes::generate_prime as crypto_primes_generate;
+use crypto_primes::{
+    generate_prime as crypto_primes_generate,
+    generate_prime_with_rng as crypto_primes_generate_with_rng,
+};
+
+use std::{clone::Clone, cmp::Ordering, convert::TryFrom, fmt, str::FromStr};
 
-use std::{clone::Clone, fmt};
+/// Number of limbs above which `mul_tuned` switches from schoolbook to Karatsuba multiplication
+pub const KARATSUBA_THRESHOLD: usize = 32;
 
 /// A trait that define a big int interface. We need to do basic arithmetic operations with them,
 /// computing greater common divisor, square root, generate a random int mod `modulus`, cast a u128
-/// into a big int.
-pub trait BigIntTrait: PartialEq + PartialOrd + Clone + fmt::Display + fmt::Debug {
+/// into a big int, parse a big int from a decimal string.
+pub trait BigIntTrait:
+    PartialEq + PartialOrd + Clone + fmt::Display + fmt::Debug + FromStr
+{
     fn add(&self, other: &Self) -> Self;
     fn sub(&self, other: &Self) -> Self;
     fn mul(&self, other: &Self) -> Self;
@@ -42,26 +53,281 @@ pub trait BigIntTrait: PartialEq + PartialOrd + Clone + fmt::Display + fmt::Debu
     }
     fn div(&self, other: &Self) -> Self;
     fn rem(&self, other: &Self) -> Self;
+    /// Adds two big ints, returning `None` if the sum overflows
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+    /// Multiplies two big ints, returning `None` if the product overflows
+    fn checked_mul(&self, other: &Self) -> Option<Self>;
     fn gcd(&self, other: &Self) -> Self;
     fn sqrt(&self) -> Self;
+    /// Samples a uniformly random integer in `[0, modulus)`. Implementations use rejection
+    /// sampling, so the result carries no modulo bias whatever the modulus.
     fn random_mod(modulus: &Self) -> Self;
+    /// Same as `random_mod`, drawing the randomness from `rng`
+    fn random_mod_with_rng<R: RngCore + CryptoRng>(modulus: &Self, rng: &mut R) -> Self;
     fn from_u128(n: u128) -> Self;
     fn is_zero(&self) -> bool;
     fn generate_prime(bit_length: Option<usize>) -> Self;
+    /// Checks whether the big int is a prime, with a probabilistic primality test
+    fn is_probably_prime(&self) -> bool;
+    /// Same as `generate_prime`, drawing the randomness from `rng`
+    fn generate_prime_with_rng<R: RngCore + CryptoRng>(
+        bit_length: Option<usize>,
+        rng: &mut R,
+    ) -> Self;
     fn zero() -> Self {
         Self::from_u128(0)
     }
     fn one() -> Self {
         Self::from_u128(1)
     }
+    /// Returns `self - other`, or zero if `other` is larger than `self`
+    fn saturating_sub(&self, other: &Self) -> Self {
+        if self < other {
+            Self::zero()
+        } else {
+            self.sub(other)
+        }
+    }
+    /// Returns `-self mod modulus`, i.e. `(modulus - self % modulus) % modulus`
+    fn neg_mod(&self, modulus: &Self) -> Self {
+        modulus.sub(&self.rem(modulus)).rem(modulus)
+    }
+    /// Compares the canonical residues of `self` and `other` in `[0, modulus)`
+    fn cmp_mod(&self, other: &Self, modulus: &Self) -> Ordering {
+        let canonical = |n: &Self| {
+            let r = n.rem(modulus);
+            if r < Self::zero() {
+                r.add(modulus)
+            } else {
+                r
+            }
+        };
+        let (a, b) = (canonical(self), canonical(other));
+        if a < b {
+            Ordering::Less
+        } else if a == b {
+            Ordering::Equal
+        } else {
+            Ordering::Greater
+        }
+    }
+    /// Returns the width-`window` non-adjacent form of the big int, least significant digit
+    /// first: odd digits below `2^(window - 1)` in absolute value, each followed by at least
+    /// `window - 1` zeros, such that `self = sum(naf[i] * 2^i)`. Panics unless `window` is in
+    /// `2..=8`.
+    fn to_naf(&self, window: usize) -> Vec<i8> {
+        if !(2..=8).contains(&window) {
+            panic!(
+                "cannot compute the NAF of '{}' with a window of {} bits",
+                self, window
+            );
+        }
+        let mut bits: Vec<u8> = self
+            .to_be_bytes()
+            .iter()
+            .rev()
+            .flat_map(|byte| (0..8).map(move |i| (byte >> i) & 1))
+            .collect();
+        let mut naf = Vec::with_capacity(bits.len() + 1);
+        let mut i = 0;
+        while i < bits.len() {
+            if bits[i] == 1 {
+                bits.resize(bits.len().max(i + window), 0);
+                let v = bits[i..i + window]
+                    .iter()
+                    .rev()
+                    .fold(0_i16, |v, bit| 2 * v + *bit as i16);
+                let d = if v >= 1 << (window - 1) {
+                    v - (1 << window)
+                } else {
+                    v
+                };
+                bits[i..i + window].fill(0);
+                if d < 0 {
+                    // subtracting the negative digit carries into the bits above the window
+                    let mut j = i + window;
+                    while j < bits.len() && bits[j] == 1 {
+                        bits[j] = 0;
+                        j += 1;
+                    }
+                    if j == bits.len() {
+                        bits.push(1);
+                    } else {
+                        bits[j] = 1;
+                    }
+                }
+                naf.push(d as i8);
+            } else {
+                naf.push(0);
+            }
+            i += 1;
+        }
+        while naf.last() == Some(&0) {
+            naf.pop();
+        }
+        naf
+    }
+    /// Returns the minimal big-endian bytes of the big int (no bytes for zero)
+    fn to_be_bytes(&self) -> Vec<u8> {
+        let base = Self::from_u128(256);
+        let mut n = self.clone();
+        let mut bytes = Vec::new();
+        while !n.is_zero() {
+            // read the bits of the low byte off the remainder, computed once
+            let mut r = n.rem(&base);
+            let mut byte = 0;
+            for bit in (0..8).rev() {
+                let power = Self::from_u128(1 << bit);
+                if r >= power {
+                    r = r.sub(&power);
+                    byte |= 1 << bit;
+                }
+            }
+            bytes.push(byte);
+            n = n.div(&base);
+        }
+        bytes.reverse();
+        bytes
+    }
+    /// Builds a big int from big-endian bytes, returning `None` if it overflows
+    fn from_be_bytes(bytes: &[u8]) -> Option<Self> {
+        let base = Self::from_u128(256);
+        bytes.iter().try_fold(Self::zero(), |n, byte| {
+            n.checked_mul(&base)?
+                .checked_add(&Self::from_u128(*byte as u128))
+        })
+    }
+    /// Returns `b` if `choice` is true and `a` otherwise. Only the selection is branch-free:
+    /// `choice` is a plain `bool`, so nothing is guaranteed about how it was computed.
+    fn conditional_select(a: &Self, b: &Self, choice: bool) -> Self {
+        let c = Self::from_u128(choice as u128);
+        b.mul(&c).add(&a.mul(&Self::one().sub(&c)))
+    }
+    /// Returns `self - modulus` if `self >= modulus` and `self` otherwise, e.g. to reduce the sum
+    /// of two residues below `2*modulus`. The default compares with `PartialOrd`, which is not
+    /// constant-time.
+    fn reduce_once(&self, modulus: &Self) -> Self {
+        let subtrahend = Self::conditional_select(&Self::zero(), modulus, self >= modulus);
+        self.sub(&subtrahend)
+    }
+    /// Samples a random integer in `[0, modulus)` coprime to `modulus`, rejecting the candidates
+    /// sharing a factor with it. Panics if `modulus` is zero.
+    fn random_coprime_to(modulus: &Self) -> Self {
+        if modulus.is_zero() {
+            panic!("cannot sample an integer coprime to '{}'", modulus)
+        }
+        loop {
+            let candidate = Self::random_mod(modulus);
+            if candidate.gcd(modulus) == Self::one() {
+                return candidate;
+            }
+        }
+    }
+    /// Runs the extended euclidean algorithm on `(m, a)`, returning `gcd(a, m)` and, when it is
+    /// 1, the inverse of `a` modulo `m`
+    fn gcd_inv(a: &Self, m: &Self) -> (Self, Option<Self>) {
+        let (gcd, inverse) = Self::gcd_ext(a, m);
+        if gcd == Self::one() {
+            (gcd, Some(inverse))
+        } else {
+            (gcd, None)
+        }
+    }
+    /// Runs the extended euclidean algorithm on `(m, a)`, returning `gcd(a, m)` and `y` in
+    /// `[0, m)` such that `y * a = gcd(a, m) (mod m)`, i.e. the inverse of `a` when the gcd is 1.
+    /// There is no branch on the outcome, only on the number of euclidean steps.
+    fn gcd_ext(a: &Self, m: &Self) -> (Self, Self) {
+        let (mut x0, mut x1) = (m.clone(), a.rem(m));
+        let (mut y0, mut y1) = (Self::zero(), Self::one());
+        while !x1.is_zero() {
+            let integer_div = x0.div(&x1);
+            (x0, x1) = (x1.clone(), x0.sub(&integer_div.mul(&x1)));
+            (y0, y1) = (
+                y1.clone(),
+                y0.add(m).sub(&integer_div.mul(&y1).rem(m)).rem(m),
+            );
+        }
+        // x0 = gcd(a, m) = (y0 * a) % m
+        (x0, y0.rem(m))
+    }
+    /// Returns the Farey bound `floor(sqrt(modulus/2))`: rationals whose numerator and
+    /// denominator are both at most it are uniquely reconstructed from their residue mod `modulus`
+    fn farey_bound(modulus: &Self) -> Self {
+        modulus.div(&Self::from_u128(2)).sqrt()
+    }
 }
 
-#[derive(PartialEq, PartialOrd, Clone, Debug)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone)]
 pub struct WrappingCryptoBigInt<const L: usize = DEFAULT_LIMBS>(pub Wrapping<Uint<L>>);
 // #[derive(PartialEq, PartialOrd, Clone, Debug)]
-#[derive(Clone, Debug)]
+#[derive(Clone)]
 pub struct CheckedCryptoBigInt<const L: usize = DEFAULT_LIMBS>(pub Checked<Uint<L>>);
 
+/// Error returned when parsing a big int from a decimal string fails
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum ParseBigIntError {
+    Empty,
+    InvalidDigit(char),
+    Overflow,
+}
+
+impl fmt::Display for ParseBigIntError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseBigIntError::Empty => write!(f, "cannot parse a big int from an empty string"),
+            ParseBigIntError::InvalidDigit(c) => write!(f, "invalid decimal digit '{}'", c),
+            ParseBigIntError::Overflow => write!(f, "number too large for the big int size"),
+        }
+    }
+}
+
+impl std::error::Error for ParseBigIntError {}
+
+/// Parses a string in base `radix` (at most 36) into a `Uint<L>`, failing if it does not fit in
+/// `L` limbs
+fn parse_radix<const L: usize>(s: &str, radix: u32) -> Result<Uint<L>, ParseBigIntError> {
+    if s.is_empty() {
+        return Err(ParseBigIntError::Empty);
+    }
+    let base = Checked::new(Uint::<L>::from_u32(radix));
+    let mut n = Checked::new(Uint::<L>::ZERO);
+    for c in s.chars() {
+        let digit = c.to_digit(radix).ok_or(ParseBigIntError::InvalidDigit(c))?;
+        n = n * base + Checked::new(Uint::<L>::from_u32(digit));
+    }
+    Option::from(n.0).ok_or(ParseBigIntError::Overflow)
+}
+
+/// Parses a Wrapping big int from a decimal string
+impl<const L: usize> FromStr for WrappingCryptoBigInt<L> {
+    type Err = ParseBigIntError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_radix::<L>(s, 10).map(|n| Self(Wrapping(n)))
+    }
+}
+
+/// Parses a Checked big int from a decimal string
+impl<const L: usize> FromStr for CheckedCryptoBigInt<L> {
+    type Err = ParseBigIntError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_radix::<L>(s, 10).map(|n| Self(Checked::new(n)))
+    }
+}
+
+/// Defaults a Wrapping big int to zero
+impl<const L: usize> Default for WrappingCryptoBigInt<L> {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+/// Defaults a Checked big int to zero
+impl<const L: usize> Default for CheckedCryptoBigInt<L> {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
 /// Displays a Wrapping big int
 impl<const L: usize> fmt::Display for WrappingCryptoBigInt<L> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -86,6 +352,71 @@ impl<const L: usize> fmt::Display for CheckedCryptoBigInt<L> {
     }
 }
 
+/// Formats a `Uint` in decimal, by chunks of 9 digits
+fn fmt_decimal<const L: usize>(n: &Uint<L>, f: &mut fmt::Formatter) -> fmt::Result {
+    let chunk_divisor = NonZero::new(Limb::from_u32(1_000_000_000)).unwrap();
+    let mut chunks = Vec::new();
+    let mut n = *n;
+    loop {
+        let (q, r) = n.div_rem_limb(chunk_divisor);
+        chunks.push(r.0);
+        n = q;
+        if n.is_zero().into() {
+            break;
+        }
+    }
+    let mut digits = chunks.pop().unwrap().to_string();
+    for chunk in chunks.iter().rev() {
+        digits.push_str(&format!("{:09}", chunk));
+    }
+    f.pad_integral(true, "", &digits)
+}
+
+/// Debug-prints a Wrapping big int in decimal
+impl<const L: usize> fmt::Debug for WrappingCryptoBigInt<L> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_decimal(&self.0 .0, f)
+    }
+}
+
+/// Debug-prints a Checked big int in decimal
+impl<const L: usize> fmt::Debug for CheckedCryptoBigInt<L> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let b: bool = self.0 .0.is_some().into();
+        if b {
+            fmt_decimal(&self.0 .0.unwrap(), f)
+        } else {
+            write!(f, "None")
+        }
+    }
+}
+
+/// Formats the hexadecimal digits of a `Uint`, without leading zeros
+fn fmt_lower_hex<const L: usize>(n: &Uint<L>, f: &mut fmt::Formatter) -> fmt::Result {
+    let digits = format!("{:x}", n);
+    let digits = digits.trim_start_matches('0');
+    f.pad_integral(true, "0x", if digits.is_empty() { "0" } else { digits })
+}
+
+/// Displays a Wrapping big int in lowercase hexadecimal
+impl<const L: usize> fmt::LowerHex for WrappingCryptoBigInt<L> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_lower_hex(&self.0 .0, f)
+    }
+}
+
+/// Displays a Checked big int in lowercase hexadecimal
+impl<const L: usize> fmt::LowerHex for CheckedCryptoBigInt<L> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let b: bool = self.0 .0.is_some().into();
+        if b {
+            fmt_lower_hex(&self.0 .0.unwrap(), f)
+        } else {
+            write!(f, "(NONE VALUE)")
+        }
+    }
+}
+
 /// Checks wether two Checked big int are equal
 impl<const L: usize> std::cmp::PartialEq for CheckedCryptoBigInt<L> {
     fn eq(&self, other: &Self) -> bool {
@@ -133,6 +464,13 @@ impl BigIntTrait for BigInt {
             result
         }
     }
+    // num_bigint's big ints grow as needed and never overflow
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(self + other)
+    }
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        Some(self * other)
+    }
 
     /// Computes gcd of two &BigInt, the good-old Euclid way
     fn gcd(&self, other: &Self) -> Self {
@@ -169,14 +507,136 @@ impl BigIntTrait for BigInt {
         }
     }
 
+    fn generate_prime_with_rng<R: RngCore + CryptoRng>(
+        bit_length: Option<usize>,
+        rng: &mut R,
+    ) -> Self {
+        Self::from(rng.gen_prime(bit_length.unwrap_or(32)))
+    }
+
+    fn to_be_bytes(&self) -> Vec<u8> {
+        let (_, bytes) = self.to_bytes_be();
+        strip_leading_zeros(&bytes).to_vec()
+    }
+
+    fn is_probably_prime(&self) -> bool {
+        self.to_biguint()
+            .is_some_and(|n| num_bigint_dig::prime::probably_prime(&n, 20))
+    }
+
     fn random_mod(modulus: &Self) -> Self {
         if *modulus < BigInt::from(0_u128) {
             panic!("Try to generate a random BigInt modulo a negative number")
         }
+        // `gen_bigint_range` rejection-samples below the bound
         let mut rng = thread_rng();
-        let large_random_number: Self = rng.sample(RandomBits::new(modulus.bits() + 1));
-        large_random_number.rem(modulus)
+        rng.gen_bigint_range(&BigInt::from(0_u128), modulus)
+    }
+
+    fn random_mod_with_rng<R: RngCore + CryptoRng>(modulus: &Self, rng: &mut R) -> Self {
+        if *modulus < BigInt::from(0_u128) {
+            panic!("Try to generate a random BigInt modulo a negative number")
+        }
+        rng.gen_bigint_range(&BigInt::from(0_u128), modulus)
+    }
+}
+
+/// GMP big ints, which may outperform crypto-bigint for very large parameters. Like num_bigint's,
+/// they are signed and grow as needed.
+#[cfg(feature = "rug")]
+impl BigIntTrait for rug::Integer {
+    fn add(&self, other: &Self) -> Self {
+        Self::from(self + other)
+    }
+    fn sub(&self, other: &Self) -> Self {
+        Self::from(self - other)
+    }
+    fn mul(&self, other: &Self) -> Self {
+        Self::from(self * other)
+    }
+    fn div(&self, other: &Self) -> Self {
+        Self::from(self / other)
+    }
+    fn rem(&self, other: &Self) -> Self {
+        let result = Self::from(self % other);
+        if result < 0 {
+            result + other
+        } else {
+            result
+        }
+    }
+    // GMP's big ints grow as needed and never overflow
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(BigIntTrait::add(self, other))
+    }
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        Some(BigIntTrait::mul(self, other))
+    }
+    fn gcd(&self, other: &Self) -> Self {
+        rug::Integer::gcd(self.clone(), other)
+    }
+    fn sqrt(&self) -> Self {
+        rug::Integer::sqrt(self.clone())
+    }
+    fn from_u128(n: u128) -> Self {
+        Self::from(n)
+    }
+    fn is_zero(&self) -> bool {
+        *self == 0
+    }
+    fn generate_prime(bit_length: Option<usize>) -> Self {
+        Self::generate_prime_with_rng(bit_length, &mut thread_rng())
+    }
+    fn generate_prime_with_rng<R: RngCore + CryptoRng>(
+        bit_length: Option<usize>,
+        rng: &mut R,
+    ) -> Self {
+        // same default number of bits as num_bigint
+        let bits = bit_length.unwrap_or(32);
+        if bits < 2 {
+            panic!("cannot generate a {}-bit prime", bits);
+        }
+        loop {
+            let mut candidate = random_bits(bits, rng);
+            candidate.set_bit(bits as u32 - 1, true);
+            if BigIntTrait::is_probably_prime(&candidate) {
+                return candidate;
+            }
+        }
+    }
+    fn is_probably_prime(&self) -> bool {
+        *self > 1 && rug::Integer::is_probably_prime(self, 20) != rug::integer::IsPrime::No
+    }
+    fn to_be_bytes(&self) -> Vec<u8> {
+        self.to_digits::<u8>(rug::integer::Order::Msf)
+    }
+    fn random_mod(modulus: &Self) -> Self {
+        Self::random_mod_with_rng(modulus, &mut thread_rng())
+    }
+    fn random_mod_with_rng<R: RngCore + CryptoRng>(modulus: &Self, rng: &mut R) -> Self {
+        if *modulus <= 0 {
+            panic!("Try to generate a random BigInt modulo a non-positive number")
+        }
+        // rejection-samples below the modulus, as `random_bits` may exceed it at most twice over
+        let bits = modulus.significant_bits() as usize;
+        loop {
+            let candidate = random_bits(bits, rng);
+            if candidate < *modulus {
+                return candidate;
+            }
+        }
+    }
+}
+
+/// Samples a uniformly random GMP big int of at most `bits` bits
+#[cfg(feature = "rug")]
+fn random_bits<R: RngCore>(bits: usize, rng: &mut R) -> rug::Integer {
+    let mut bytes = vec![0; bits.div_ceil(8)];
+    rng.fill_bytes(&mut bytes);
+    if !bits.is_multiple_of(8) {
+        bytes[0] &= (1 << (bits % 8)) - 1;
     }
+    rug::Integer::from_digits(&bytes, rug::integer::Order::Msf)
 }
 
 impl<const L: usize> BigIntTrait for WrappingCryptoBigInt<L> {
@@ -195,6 +655,17 @@ impl<const L: usize> BigIntTrait for WrappingCryptoBigInt<L> {
     fn rem(&self, other: &Self) -> Self {
         Self(self.0 % NonZero::new(other.0 .0).unwrap())
     }
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Option::from(self.0 .0.checked_add(&other.0 .0)).map(|n| Self(Wrapping(n)))
+    }
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        let (lo, hi) = self.0 .0.mul_wide(&other.0 .0);
+        if hi.is_zero().into() {
+            Some(Self(Wrapping(lo)))
+        } else {
+            None
+        }
+    }
     /// Computes gcd of two &BigInt, the good-old Euclid way
     fn gcd(&self, other: &Self) -> Self {
         if self < other {
@@ -209,12 +680,19 @@ impl<const L: usize> BigIntTrait for WrappingCryptoBigInt<L> {
     fn sqrt(&self) -> Self {
         Self(Wrapping::<Uint<L>>(self.0 .0.sqrt_vartime()))
     }
+    /// crypto-bigint's `random_mod` rejection-samples below the modulus
     fn random_mod(modulus: &Self) -> Self {
         Self(Wrapping::<Uint<L>>(Uint::<L>::random_mod(
             &mut OsRng,
             &NonZero::new(modulus.0 .0).unwrap(),
         )))
     }
+    fn random_mod_with_rng<R: RngCore + CryptoRng>(modulus: &Self, rng: &mut R) -> Self {
+        Self(Wrapping::<Uint<L>>(Uint::<L>::random_mod(
+            rng,
+            &NonZero::new(modulus.0 .0).unwrap(),
+        )))
+    }
     fn from_u128(n: u128) -> Self {
         Self(Wrapping::<Uint<L>>(Uint::<L>::from(n)))
     }
@@ -224,6 +702,450 @@ impl<const L: usize> BigIntTrait for WrappingCryptoBigInt<L> {
     fn generate_prime(bit_length: Option<usize>) -> Self {
         WrappingCryptoBigInt(Wrapping(crypto_primes_generate::<L>(bit_length)))
     }
+    fn is_probably_prime(&self) -> bool {
+        crypto_primes::is_prime(&self.0 .0)
+    }
+    fn to_be_bytes(&self) -> Vec<u8> {
+        words_to_be_bytes(self.0 .0.as_words())
+    }
+    fn generate_prime_with_rng<R: RngCore + CryptoRng>(
+        bit_length: Option<usize>,
+        rng: &mut R,
+    ) -> Self {
+        WrappingCryptoBigInt(Wrapping(crypto_primes_generate_with_rng::<L>(
+            rng, bit_length,
+        )))
+    }
+    fn conditional_select(a: &Self, b: &Self, choice: bool) -> Self {
+        Self(Wrapping(Uint::<L>::conditional_select(
+            &a.0 .0,
+            &b.0 .0,
+            Choice::from(choice as u8),
+        )))
+    }
+    /// Constant-time: the comparison and the subtraction do not branch on the values
+    fn reduce_once(&self, modulus: &Self) -> Self {
+        let (n, m) = (&self.0 .0, &modulus.0 .0);
+        let subtrahend = Uint::<L>::conditional_select(&Uint::ZERO, m, !n.ct_lt(m));
+        Self(Wrapping(n.wrapping_sub(&subtrahend)))
+    }
+}
+
+/// Drops the leading zero bytes of a big-endian byte string
+fn strip_leading_zeros(bytes: &[u8]) -> &[u8] {
+    let start = bytes
+        .iter()
+        .position(|byte| *byte != 0)
+        .unwrap_or(bytes.len());
+    &bytes[start..]
+}
+
+/// Converts little-endian words to minimal big-endian bytes
+fn words_to_be_bytes(words: &[Word]) -> Vec<u8> {
+    let bytes: Vec<u8> = words
+        .iter()
+        .rev()
+        .flat_map(|word| word.to_be_bytes())
+        .collect();
+    strip_leading_zeros(&bytes).to_vec()
+}
+
+/// Adds `x` into `acc` (starting at limb `offset`), propagating the carry
+fn add_words(acc: &mut [Word], x: &[Word], offset: usize) {
+    let mut carry: WideWord = 0;
+    for (i, acc_word) in acc.iter_mut().enumerate().skip(offset) {
+        let x_word = x.get(i - offset).copied().unwrap_or(0);
+        if x_word == 0 && carry == 0 && i - offset >= x.len() {
+            break;
+        }
+        let sum = (*acc_word as WideWord) + (x_word as WideWord) + carry;
+        *acc_word = sum as Word;
+        carry = sum >> Word::BITS;
+    }
+}
+
+/// Subtracts `x` from `acc` in place, assuming `acc >= x`
+fn sub_words(acc: &mut [Word], x: &[Word]) {
+    let mut borrow = false;
+    for (i, acc_word) in acc.iter_mut().enumerate() {
+        let x_word = x.get(i).copied().unwrap_or(0);
+        let (d1, b1) = acc_word.overflowing_sub(x_word);
+        let (d2, b2) = d1.overflowing_sub(borrow as Word);
+        *acc_word = d2;
+        borrow = b1 || b2;
+    }
+}
+
+/// Multiplies two little-endian limb slices, the schoolbook way
+fn schoolbook_mul_words(a: &[Word], b: &[Word]) -> Vec<Word> {
+    let mut result = vec![0; a.len() + b.len()];
+    for (i, &a_word) in a.iter().enumerate() {
+        let mut carry: WideWord = 0;
+        for (j, &b_word) in b.iter().enumerate() {
+            let product =
+                (a_word as WideWord) * (b_word as WideWord) + (result[i + j] as WideWord) + carry;
+            result[i + j] = product as Word;
+            carry = product >> Word::BITS;
+        }
+        result[i + b.len()] = carry as Word;
+    }
+    result
+}
+
+/// Multiplies two little-endian limb slices of the same length with Karatsuba's method,
+/// falling back to schoolbook multiplication below `threshold` limbs
+fn karatsuba_mul_words(a: &[Word], b: &[Word], threshold: usize) -> Vec<Word> {
+    let n = a.len();
+    if n <= threshold.max(3) {
+        return schoolbook_mul_words(a, b);
+    }
+    let m = n / 2;
+    let (a0, a1) = a.split_at(m);
+    let (b0, b1) = b.split_at(m);
+
+    // z0 = a0*b0, z2 = a1*b1, z1 = (a0 + a1)*(b0 + b1) - z0 - z2
+    let z0 = karatsuba_mul_words(a0, b0, threshold);
+    let z2 = karatsuba_mul_words(a1, b1, threshold);
+    let half = n - m + 1;
+    let (mut a01, mut b01) = (vec![0; half], vec![0; half]);
+    add_words(&mut a01, a0, 0);
+    add_words(&mut a01, a1, 0);
+    add_words(&mut b01, b0, 0);
+    add_words(&mut b01, b1, 0);
+    let mut z1 = karatsuba_mul_words(&a01, &b01, threshold);
+    sub_words(&mut z1, &z0);
+    sub_words(&mut z1, &z2);
+
+    let mut result = vec![0; 2 * n + 2];
+    add_words(&mut result, &z0, 0);
+    add_words(&mut result, &z1, m);
+    add_words(&mut result, &z2, 2 * m);
+    result.truncate(2 * n);
+    result
+}
+
+impl<const L: usize> WrappingCryptoBigInt<L> {
+    /// Returns the limbs of the big int, least significant first
+    pub fn as_limbs(&self) -> &[Limb] {
+        self.0 .0.as_limbs()
+    }
+
+    /// Builds a big int from its limbs, least significant first. Missing high limbs are zero;
+    /// panics if there are more than `L` limbs.
+    pub fn from_limbs(limbs: &[Limb]) -> Self {
+        if limbs.len() > L {
+            panic!(
+                "cannot build a {}-limb big int from {} limbs",
+                L,
+                limbs.len()
+            );
+        }
+        let mut n = Uint::<L>::ZERO;
+        n.as_limbs_mut()[..limbs.len()].copy_from_slice(limbs);
+        Self(Wrapping(n))
+    }
+
+    /// Parses a big-endian hexadecimal string, with an optional `0x` prefix. As in Rust
+    /// literals, underscores (and whitespace) may be used to group the digits.
+    pub fn from_hex(s: &str) -> Result<Self, ParseBigIntError> {
+        let trimmed = s.trim();
+        let digits: String = trimmed
+            .strip_prefix("0x")
+            .or_else(|| trimmed.strip_prefix("0X"))
+            .unwrap_or(trimmed)
+            .chars()
+            .filter(|c| *c != '_' && !c.is_whitespace())
+            .collect();
+        parse_radix::<L>(&digits, 16).map(|n| Self(Wrapping(n)))
+    }
+
+    /// Builds a big int from 128-bit chunks, least significant first. Panics if the value does
+    /// not fit in `L` limbs.
+    pub fn from_u128_array(chunks: &[u128]) -> Self {
+        let word_bits = Word::BITS as usize;
+        let mut limbs: Vec<Limb> = chunks
+            .iter()
+            .flat_map(|chunk| {
+                (0..128 / word_bits).map(move |i| Limb((chunk >> (i * word_bits)) as Word))
+            })
+            .collect();
+        while matches!(limbs.last(), Some(Limb(0))) {
+            limbs.pop();
+        }
+        Self::from_limbs(&limbs)
+    }
+
+    /// Returns the number of trailing zero bits, i.e. the 2-adic valuation (`Uint::<L>::BITS` for
+    /// zero)
+    pub fn trailing_zeros(&self) -> u32 {
+        self.0 .0.trailing_zeros() as u32
+    }
+
+    /// Returns a base proving with the Miller-Rabin test that the big int is composite, or `None`
+    /// if it is probably prime. The bases are the primes up to `37` (enough to decide primality
+    /// below 3.3e24), tried in order so that the witness is reproducible. Panics if the big int
+    /// is less than 2.
+    pub fn composite_witness(&self) -> Option<Self> {
+        let n = self.0 .0;
+        if n < Uint::<L>::from_u8(2) {
+            panic!("cannot test the primality of '{}'", self);
+        }
+        let two = Self::from_u128(2);
+        if !bool::from(n.is_odd()) {
+            return if *self == two { None } else { Some(two) };
+        }
+        let n_minus_one = n.wrapping_sub(&Uint::ONE);
+        let s = n_minus_one.trailing_zeros();
+        let d = n_minus_one.shr_vartime(s);
+        let params = DynResidueParams::new(&n);
+        [2u8, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37]
+            .iter()
+            .map(|a| Uint::<L>::from_u8(*a))
+            .take_while(|a| *a < n_minus_one)
+            .find(|a| {
+                let mut x = DynResidue::new(a, params).pow(&d).retrieve();
+                if x == Uint::ONE || x == n_minus_one {
+                    return false;
+                }
+                for _ in 1..s {
+                    x = DynResidue::new(&x, params).square().retrieve();
+                    if x == n_minus_one {
+                        return false;
+                    }
+                }
+                true
+            })
+            .map(|a| Self(Wrapping(a)))
+    }
+
+    /// Checks whether the big int is a power of two (zero is not)
+    pub fn is_power_of_two(&self) -> bool {
+        let n = &self.0 .0;
+        n.bits() != 0 && n.trailing_zeros() + 1 == n.bits()
+    }
+
+    /// Resizes the big int to `L2` limbs, panics if its value does not fit in `L2` limbs
+    pub fn resize<const L2: usize>(&self) -> WrappingCryptoBigInt<L2> {
+        if self.0 .0.bits() > Uint::<L2>::BITS {
+            panic!("cannot resize '{}' to {} limbs", self, L2);
+        }
+        WrappingCryptoBigInt(Wrapping(self.0 .0.resize::<L2>()))
+    }
+
+    /// Raises the big int to the power `exponent` by square-and-multiply, returning `None` if the
+    /// result does not fit in `L` limbs
+    pub fn checked_pow(&self, exponent: u32) -> Option<Self> {
+        let mut exponent = exponent;
+        let mut x = self.clone();
+        let mut y = Self::one();
+        while exponent > 0 {
+            if exponent % 2 == 1 {
+                y = y.checked_mul(&x)?;
+            }
+            exponent /= 2;
+            if exponent > 0 {
+                x = x.checked_mul(&x)?;
+            }
+        }
+        Some(y)
+    }
+
+    /// Multiplies two big ints into a `LW`-limb big int, so that the product never wraps.
+    /// `LW` plays the role of `2*L`, which const generics cannot express: panics if `LW < 2*L`.
+    pub fn mul_wide<const LW: usize>(&self, other: &Self) -> WrappingCryptoBigInt<LW> {
+        if LW < 2 * L {
+            panic!(
+                "cannot widen a product of {}-limb big ints to {} limbs",
+                L, LW
+            );
+        }
+        let (a, b) = (self.0 .0.resize::<LW>(), other.0 .0.resize::<LW>());
+        WrappingCryptoBigInt(Wrapping(a.wrapping_mul(&b)))
+    }
+
+    /// Multiplies two big ints (wrapping at `L` limbs), using Karatsuba multiplication when `L`
+    /// is above `KARATSUBA_THRESHOLD` and crypto-bigint's schoolbook multiplication otherwise
+    pub fn mul_tuned(&self, other: &Self) -> Self {
+        if L <= KARATSUBA_THRESHOLD {
+            return self.mul(other);
+        }
+        let product = karatsuba_mul_words(
+            self.0 .0.as_words(),
+            other.0 .0.as_words(),
+            KARATSUBA_THRESHOLD,
+        );
+        let mut words = [0; L];
+        words.copy_from_slice(&product[..L]);
+        Self(Wrapping(Uint::<L>::from_words(words)))
+    }
+}
+
+/// Computes the gcd of two big ints of possibly different sizes, after resizing both to `LG` limbs
+pub fn gcd_mixed<const LA: usize, const LB: usize, const LG: usize>(
+    a: &WrappingCryptoBigInt<LA>,
+    b: &WrappingCryptoBigInt<LB>,
+) -> WrappingCryptoBigInt<LG> {
+    a.resize::<LG>().gcd(&b.resize::<LG>())
+}
+
+/// Big ints of a fixed capacity, known at compile time
+pub trait Bounded {
+    /// Number of bits of the largest representable value
+    const MAX_BITS: usize;
+    /// Returns whether values of `bits` bits are representable
+    fn fits(bits: usize) -> bool {
+        bits <= Self::MAX_BITS
+    }
+}
+
+impl<const L: usize> Bounded for WrappingCryptoBigInt<L> {
+    const MAX_BITS: usize = Uint::<L>::BITS;
+}
+
+impl<const L: usize> Bounded for CheckedCryptoBigInt<L> {
+    const MAX_BITS: usize = Uint::<L>::BITS;
+}
+
+/// num_bigint's big ints grow as needed
+impl Bounded for BigInt {
+    const MAX_BITS: usize = usize::MAX;
+}
+
+/// Error returned when a big int does not fit in a `u128`
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct U128Overflow;
+
+impl fmt::Display for U128Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "big int does not fit in a u128")
+    }
+}
+
+impl std::error::Error for U128Overflow {}
+
+/// Converts minimal big-endian bytes into a `u128`
+pub(crate) fn be_bytes_to_u128(bytes: &[u8]) -> Result<u128, U128Overflow> {
+    if bytes.len() > 16 {
+        return Err(U128Overflow);
+    }
+    Ok(bytes.iter().fold(0, |n, byte| (n << 8) | *byte as u128))
+}
+
+impl<const L: usize> TryFrom<&WrappingCryptoBigInt<L>> for u128 {
+    type Error = U128Overflow;
+    fn try_from(n: &WrappingCryptoBigInt<L>) -> Result<Self, Self::Error> {
+        be_bytes_to_u128(&n.to_be_bytes())
+    }
+}
+
+impl<const L: usize> TryFrom<&CheckedCryptoBigInt<L>> for u128 {
+    type Error = U128Overflow;
+    fn try_from(n: &CheckedCryptoBigInt<L>) -> Result<Self, Self::Error> {
+        if bool::from(n.0 .0.is_none()) {
+            return Err(U128Overflow);
+        }
+        be_bytes_to_u128(&n.to_be_bytes())
+    }
+}
+
+/// Number of candidates drawn by `random_prime_in_range` before giving up
+pub const PRIME_SEARCH_ATTEMPTS: usize = 10_000;
+
+/// Error returned when no prime is found in a range
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct NoPrimeFound;
+
+impl fmt::Display for NoPrimeFound {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "no prime found after {} attempts", PRIME_SEARCH_ATTEMPTS)
+    }
+}
+
+impl std::error::Error for NoPrimeFound {}
+
+/// Draws random candidates in `[lo, hi)` until one is prime, giving up after
+/// `PRIME_SEARCH_ATTEMPTS` candidates. Panics if the range is empty.
+pub fn random_prime_in_range<T: BigIntTrait>(lo: &T, hi: &T) -> Result<T, NoPrimeFound> {
+    if hi <= lo {
+        panic!(
+            "cannot draw a prime in the empty range ['{}', '{}')",
+            lo, hi
+        );
+    }
+    let width = hi.sub(lo);
+    (0..PRIME_SEARCH_ATTEMPTS)
+        .map(|_| lo.add(&T::random_mod(&width)))
+        .find(|candidate| candidate.is_probably_prime())
+        .ok_or(NoPrimeFound)
+}
+
+/// Computes the gcd of all the big ints in `values` (zero for an empty slice)
+pub fn gcd_batch<T: BigIntTrait>(values: &[T]) -> T {
+    values.iter().fold(T::zero(), |acc, value| acc.gcd(value))
+}
+
+/// Checks that no two big ints in `values` share a common factor
+pub fn are_pairwise_coprime<T: BigIntTrait>(values: &[T]) -> bool {
+    values
+        .iter()
+        .enumerate()
+        .all(|(i, a)| values[i + 1..].iter().all(|b| abs(&a.gcd(b)) == T::one()))
+}
+
+/// Returns the absolute value of `n`
+pub(crate) fn abs<T: BigIntTrait>(n: &T) -> T {
+    if *n < T::zero() {
+        T::zero().sub(n)
+    } else {
+        n.clone()
+    }
+}
+
+#[cfg(feature = "num-traits")]
+impl_big_int_trait_op!(
+    WrappingCryptoBigInt,
+    (Add, add),
+    (Sub, sub),
+    (Mul, mul),
+    (Div, div),
+    (Rem, rem)
+);
+
+#[cfg(feature = "num-traits")]
+impl<const L: usize> num_traits::Zero for WrappingCryptoBigInt<L> {
+    fn zero() -> Self {
+        <Self as BigIntTrait>::zero()
+    }
+    fn is_zero(&self) -> bool {
+        <Self as BigIntTrait>::is_zero(self)
+    }
+}
+
+#[cfg(feature = "num-traits")]
+impl<const L: usize> num_traits::One for WrappingCryptoBigInt<L> {
+    fn one() -> Self {
+        <Self as BigIntTrait>::one()
+    }
+}
+
+#[cfg(feature = "num-traits")]
+impl<const L: usize> num_traits::Num for WrappingCryptoBigInt<L> {
+    type FromStrRadixErr = ParseBigIntError;
+    fn from_str_radix(s: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        parse_radix::<L>(s, radix).map(|n| Self(Wrapping(n)))
+    }
+}
+
+/// Builds the big int from `L` arbitrary words, least significant first
+#[cfg(feature = "arbitrary")]
+impl<'a, const L: usize> arbitrary::Arbitrary<'a> for WrappingCryptoBigInt<L> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let mut words: [Word; L] = [0; L];
+        for word in words.iter_mut() {
+            *word = u.arbitrary()?;
+        }
+        Ok(Self(Wrapping(Uint::<L>::from_words(words))))
+    }
 }
 
 impl<const L: usize> BigIntTrait for CheckedCryptoBigInt<L> {
@@ -250,6 +1172,22 @@ impl<const L: usize> BigIntTrait for CheckedCryptoBigInt<L> {
                 .and_then(|n| other.0 .0.and_then(|m| n.checked_rem(&m))),
         ))
     }
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        let sum = self.add(other);
+        if sum.0 .0.is_some().into() {
+            Some(sum)
+        } else {
+            None
+        }
+    }
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        let product = self.mul(other);
+        if product.0 .0.is_some().into() {
+            Some(product)
+        } else {
+            None
+        }
+    }
     /// Computes gcd of two &BigInt, the good-old Euclid way
     fn gcd(&self, other: &Self) -> Self {
         Self(Checked(self.0 .0.and_then(|n| {
@@ -267,11 +1205,17 @@ impl<const L: usize> BigIntTrait for CheckedCryptoBigInt<L> {
     fn sqrt(&self) -> Self {
         Self(Checked::<Uint<L>>(self.0 .0.map(|x| x.sqrt_vartime())))
     }
+    /// crypto-bigint's `random_mod` rejection-samples below the modulus
     fn random_mod(modulus: &Self) -> Self {
         Self(Checked(modulus.0 .0.map(|n| {
             Uint::<L>::random_mod(&mut OsRng, &NonZero::new(n).unwrap())
         })))
     }
+    fn random_mod_with_rng<R: RngCore + CryptoRng>(modulus: &Self, rng: &mut R) -> Self {
+        Self(Checked(modulus.0 .0.map(|n| {
+            Uint::<L>::random_mod(rng, &NonZero::new(n).unwrap())
+        })))
+    }
     fn from_u128(n: u128) -> Self {
         Self(Checked::new(Uint::<L>::from(n)))
     }
@@ -281,14 +1225,34 @@ impl<const L: usize> BigIntTrait for CheckedCryptoBigInt<L> {
     fn generate_prime(bit_length: Option<usize>) -> Self {
         CheckedCryptoBigInt(Checked::new(crypto_primes_generate::<L>(bit_length)))
     }
+    /// An overflowed big int is not a prime
+    fn is_probably_prime(&self) -> bool {
+        Option::<Uint<L>>::from(self.0 .0).is_some_and(|n| crypto_primes::is_prime(&n))
+    }
+    /// An overflowed big int has no bytes
+    fn to_be_bytes(&self) -> Vec<u8> {
+        Option::<Uint<L>>::from(self.0 .0)
+            .map_or_else(Vec::new, |n| words_to_be_bytes(n.as_words()))
+    }
+    fn generate_prime_with_rng<R: RngCore + CryptoRng>(
+        bit_length: Option<usize>,
+        rng: &mut R,
+    ) -> Self {
+        CheckedCryptoBigInt(Checked::new(crypto_primes_generate_with_rng::<L>(
+            rng, bit_length,
+        )))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::shared::limbs_for_bits;
 
     const L: usize = DEFAULT_LIMBS;
     type T = WrappingCryptoBigInt<L>;
+    type W128 = WrappingCryptoBigInt<{ limbs_for_bits(128) }>;
+    type W256 = WrappingCryptoBigInt<{ limbs_for_bits(256) }>;
 
     #[test]
     fn add_bigint() {
@@ -305,15 +1269,629 @@ mod tests {
         simple_tester(129812, 92373829187);
     }
 
+    #[test]
+    fn default_bigint() {
+        assert_eq!(T::default(), T::zero());
+        assert_eq!(
+            CheckedCryptoBigInt::<L>::default(),
+            CheckedCryptoBigInt::zero()
+        );
+    }
+
+    #[test]
+    fn mul_tuned_matches_mul() {
+        fn simple_tester<const L1: usize>() {
+            let max = WrappingCryptoBigInt::<L1>(Wrapping(Uint::<L1>::MAX));
+            for _ in 0..10 {
+                let (a, b) = (
+                    WrappingCryptoBigInt::<L1>::random_mod(&max),
+                    WrappingCryptoBigInt::<L1>::random_mod(&max),
+                );
+                assert_eq!(a.mul_tuned(&b), a.mul(&b));
+            }
+            assert_eq!(max.mul_tuned(&max), max.mul(&max));
+        }
+
+        simple_tester::<4>();
+        simple_tester::<33>();
+        simple_tester::<64>();
+        simple_tester::<127>();
+    }
+
+    #[test]
+    fn random_mod_is_uniform() {
+        fn simple_tester<U: BigIntTrait>() {
+            // 6 is not a power of two: reducing 4 random bits modulo 6 would make the residues
+            // 0..=3 half again as likely as 4 and 5
+            let (modulus, samples) = (6, 12000);
+            let mut counts = [0; 6];
+            for _ in 0..samples {
+                let n = U::random_mod(&U::from_u128(modulus));
+                let i = (0..modulus).position(|k| n == U::from_u128(k)).unwrap();
+                counts[i] += 1;
+            }
+            for count in counts {
+                assert!((1800..2200).contains(&count), "biased counts: {:?}", counts);
+            }
+        }
+
+        simple_tester::<BigInt>();
+        simple_tester::<T>();
+    }
+
+    #[test]
+    fn parse_bigint() {
+        assert_eq!("7919".parse::<T>(), Ok(T::from_u128(7919)));
+        assert_eq!(
+            "340282366920938463463374607431768211455".parse::<CheckedCryptoBigInt<L>>(),
+            Ok(CheckedCryptoBigInt::from_u128(u128::MAX))
+        );
+        assert_eq!("".parse::<T>(), Err(ParseBigIntError::Empty));
+        assert_eq!("12a".parse::<T>(), Err(ParseBigIntError::InvalidDigit('a')));
+        assert_eq!(
+            "18446744073709551616".parse::<WrappingCryptoBigInt<{ limbs_for_bits(64) }>>(),
+            Err(ParseBigIntError::Overflow)
+        );
+    }
+
+    #[test]
+    fn gcd_mixed_sizes() {
+        let a = W128::from_u128(7919 * 12);
+        let b = W256::from_u128(7919 * 18);
+        assert_eq!(gcd_mixed::<2, 4, 4>(&a, &b), W256::from_u128(7919 * 6));
+        assert_eq!(gcd_mixed::<4, 2, 2>(&b, &a), W128::from_u128(7919 * 6));
+    }
+
+    #[test]
+    fn random_prime_in_range() {
+        fn simple_tester<U: BigIntTrait>() {
+            let (lo, hi) = (U::from_u128(1 << 40), U::from_u128((1 << 40) + 10_000));
+            let p = super::random_prime_in_range(&lo, &hi).unwrap();
+            assert!(lo <= p && p < hi);
+            assert!(p.is_probably_prime());
+        }
+
+        simple_tester::<BigInt>();
+        simple_tester::<W128>();
+        simple_tester::<CheckedCryptoBigInt<{ limbs_for_bits(128) }>>();
+        // there is no prime between 24 and 28
+        assert_eq!(
+            super::random_prime_in_range(&W128::from_u128(24), &W128::from_u128(29)),
+            Err(NoPrimeFound)
+        );
+        assert!(!W128::from_u128(7919 * 37).is_probably_prime());
+        let max = CheckedCryptoBigInt::<{ limbs_for_bits(128) }>::from_u128(u128::MAX);
+        assert!(!max.add(&BigIntTrait::one()).is_probably_prime());
+    }
+
+    #[test]
+    fn gcd_of_slices() {
+        let values: Vec<T> = [7919 * 12, 7919 * 18, 7919 * 30]
+            .into_iter()
+            .map(T::from_u128)
+            .collect();
+        assert_eq!(gcd_batch(&values), T::from_u128(7919 * 6));
+        assert_eq!(gcd_batch::<T>(&[]), T::zero());
+        assert!(!are_pairwise_coprime(&values));
+
+        let primes: Vec<BigInt> = [7919, 37, 41, 5897, 7759]
+            .into_iter()
+            .map(BigInt::from_u128)
+            .collect();
+        assert!(are_pairwise_coprime(&primes));
+        let not_coprime: Vec<BigInt> = [35, 11, 21].into_iter().map(BigInt::from_u128).collect();
+        assert!(!are_pairwise_coprime(&not_coprime));
+        // gcd(-1, 3) is -1 with num_bigint
+        assert!(are_pairwise_coprime(&[BigInt::from(-1), BigInt::from(3)]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn resize_too_small() {
+        let _ = W128::from_u128(u128::MAX).resize::<{ limbs_for_bits(64) }>();
+    }
+
+    #[cfg(feature = "num-traits")]
+    #[test]
+    fn std_ops() {
+        let (a, b) = (T::from_u128(1337), T::from_u128(42));
+        assert_eq!(a.clone() + b.clone(), T::from_u128(1379));
+        assert_eq!(a.clone() - b.clone(), T::from_u128(1295));
+        assert_eq!(a.clone() * b.clone(), T::from_u128(56154));
+        assert_eq!(a.clone() / b.clone(), T::from_u128(31));
+        assert_eq!(a % b, T::from_u128(35));
+    }
+
+    #[cfg(feature = "num-traits")]
+    #[test]
+    fn std_ops_on_references() {
+        let (a, b) = (T::from_u128(1337), T::from_u128(42));
+        let expected = T::from_u128(35);
+        assert_eq!(&a % &b, expected);
+        assert_eq!(a.clone() % &b, expected);
+        assert_eq!(&a % b.clone(), expected);
+        assert_eq!(a.clone() % b.clone(), expected);
+        assert_eq!(&a + &b, T::from_u128(1379));
+        assert_eq!(&a * b, T::from_u128(56154));
+    }
+
+    #[cfg(feature = "num-traits")]
+    #[test]
+    fn num_traits() {
+        use num_traits::{Num, One, Zero};
+        assert_eq!(<T as Zero>::zero() + <T as One>::one(), T::from_u128(1));
+        assert!(<T as Zero>::is_zero(&<T as Zero>::zero()));
+        assert_eq!(T::from_str_radix("ff", 16), Ok(T::from_u128(255)));
+        assert_eq!(T::from_str_radix("1337", 10), Ok(T::from_u128(1337)));
+    }
+
+    #[test]
+    fn lower_hex_bigint() {
+        assert_eq!(format!("{:x}", T::from_u128(7919)), "1eef");
+        assert_eq!(format!("{:#x}", T::from_u128(7919)), "0x1eef");
+        assert_eq!(format!("{:x}", T::zero()), "0");
+        assert_eq!(
+            format!("{:x}", CheckedCryptoBigInt::<L>::from_u128(255)),
+            "ff"
+        );
+    }
+
+    #[test]
+    fn checked_mul_overflow() {
+        let two_64 = W128::from_u128(1 << 64);
+        assert_eq!(two_64.checked_mul(&two_64), None);
+        let (a, b) = (
+            W128::from_u128((1 << 64) - 1),
+            W128::from_u128((1 << 64) + 1),
+        );
+        assert_eq!(a.checked_mul(&b), Some(W128::from_u128(u128::MAX)));
+        let max = W128::from_u128(u128::MAX);
+        assert_eq!(max.checked_mul(&W128::one()), Some(max.clone()));
+        assert_eq!(max.checked_mul(&W128::from_u128(2)), None);
+    }
+
+    #[test]
+    fn mul_wide_preserves_product() {
+        let max = W128::from_u128(u128::MAX);
+        assert_eq!(max.checked_mul(&max), None);
+        // (2^128 - 1)^2 = 2^256 - 2^129 + 1
+        let expected = W256::from_u128_array(&[1, u128::MAX - 1]);
+        assert_eq!(max.mul_wide::<{ limbs_for_bits(256) }>(&max), expected);
+    }
+
+    #[test]
+    #[should_panic]
+    fn mul_wide_too_narrow() {
+        let a = W128::from_u128(3);
+        let _ = a.mul_wide::<{ limbs_for_bits(192) }>(&a);
+    }
+
+    #[test]
+    fn debug_bigint() {
+        assert_eq!(format!("{:?}", T::from_u128(7919)), "7919");
+        assert_eq!(format!("{:?}", T::zero()), "0");
+        assert_eq!(
+            format!("{:?}", T::from_u128(u128::MAX)),
+            "340282366920938463463374607431768211455"
+        );
+        assert_eq!(
+            format!("{:?}", CheckedCryptoBigInt::<L>::from_u128(1_000_000_000)),
+            "1000000000"
+        );
+        let none = CheckedCryptoBigInt::<L>::zero().div(&CheckedCryptoBigInt::zero());
+        assert_eq!(format!("{:?}", none), "None");
+    }
+
+    #[test]
+    fn farey_bound() {
+        fn simple_tester(modulus: u128) {
+            let bound = T::farey_bound(&T::from_u128(modulus));
+            let two = T::from_u128(2);
+            assert!(two.mul(&bound.pow(2)) <= T::from_u128(modulus));
+            assert!(two.mul(&bound.add(&T::one()).pow(2)) > T::from_u128(modulus));
+        }
+
+        assert_eq!(T::farey_bound(&T::from_u128(7919)), T::from_u128(62));
+        simple_tester(7919);
+        simple_tester(5897);
+        simple_tester(200);
+        simple_tester(201);
+    }
+
+    #[test]
+    fn limbs_round_trip() {
+        let x = T::from_u128(0x1234_5678_9abc_def0_1122_3344_5566_7788);
+        assert_eq!(x.as_limbs().len(), L);
+        assert_eq!(T::from_limbs(x.as_limbs()), x);
+        let short = W128::from_u128(1337);
+        assert_eq!(T::from_limbs(short.as_limbs()), T::from_u128(1337));
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_too_many_limbs() {
+        let _ = WrappingCryptoBigInt::<1>::from_limbs(T::zero().as_limbs());
+    }
+
+    #[test]
+    fn from_hex() {
+        let expected = T::from_u128(0xdead_beef_0123_4567_89ab_cdef);
+        assert_eq!(
+            T::from_hex("deadbeef0123456789abcdef"),
+            Ok(expected.clone())
+        );
+        assert_eq!(
+            T::from_hex("0xDEAD_BEEF_0123_4567_89AB_CDEF"),
+            Ok(expected.clone())
+        );
+        assert_eq!(
+            T::from_hex(" 0xdead_beef 0123_4567\n89ab_cdef "),
+            Ok(expected.clone())
+        );
+        assert_eq!(T::from_hex(&expected.to_string()), Ok(expected));
+        assert_eq!(T::from_hex("0x_"), Err(ParseBigIntError::Empty));
+        assert_eq!(
+            T::from_hex("0xbeeg"),
+            Err(ParseBigIntError::InvalidDigit('g'))
+        );
+        assert_eq!(
+            WrappingCryptoBigInt::<{ limbs_for_bits(64) }>::from_hex("1_0000_0000_0000_0000"),
+            Err(ParseBigIntError::Overflow)
+        );
+    }
+
+    #[test]
+    fn composite_witness() {
+        let witness = |n| W128::from_u128(n).composite_witness();
+        for p in [2, 3, 5, 37, 7919, 2147483647] {
+            assert_eq!(witness(p), None);
+        }
+        assert_eq!(witness(7919 * 2), Some(W128::from_u128(2)));
+        // the Carmichael number 561 = 3*11*17 fools the Fermat test, not Miller-Rabin
+        assert_eq!(witness(561), Some(W128::from_u128(2)));
+        // 2047 = 23*89 is a strong pseudoprime to base 2
+        assert_eq!(witness(2047), Some(W128::from_u128(3)));
+        assert!(witness(7919 * 7927).is_some());
+    }
+
+    #[test]
+    #[should_panic]
+    fn composite_witness_of_one() {
+        T::one().composite_witness();
+    }
+
+    #[test]
+    fn trailing_zeros() {
+        assert_eq!(T::from_u128(48).trailing_zeros(), 4);
+        assert_eq!(T::from_u128(7919).trailing_zeros(), 0);
+        assert_eq!(T::from_u128(1 << 100).trailing_zeros(), 100);
+        assert_eq!(W128::zero().trailing_zeros(), 128);
+
+        assert!(T::from_u128(64).is_power_of_two());
+        assert!(T::one().is_power_of_two());
+        assert!(!T::from_u128(48).is_power_of_two());
+        assert!(!T::zero().is_power_of_two());
+    }
+
+    #[test]
+    fn from_u128_array() {
+        // 2^255 - 19
+        let chunks = [u128::MAX - 18, u128::MAX >> 1];
+        let expected: T =
+            "57896044618658097711785492504343953926634992332820282019728792003956564819949"
+                .parse()
+                .unwrap();
+        assert_eq!(T::from_u128_array(&chunks), expected);
+        assert_eq!(
+            W256::from_u128_array(&[chunks[0], chunks[1], 0]),
+            expected.resize::<{ limbs_for_bits(256) }>()
+        );
+        assert_eq!(T::from_u128_array(&[]), T::zero());
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_too_many_u128() {
+        let _ = W128::from_u128_array(&[0, 1]);
+    }
+
+    #[test]
+    fn conditional_select() {
+        fn simple_tester<U: BigIntTrait>() {
+            let (a, b) = (U::from_u128(38), U::from_u128(4919));
+            assert_eq!(U::conditional_select(&a, &b, false), a);
+            assert_eq!(U::conditional_select(&a, &b, true), b);
+        }
+
+        simple_tester::<BigInt>();
+        simple_tester::<T>();
+        simple_tester::<CheckedCryptoBigInt<L>>();
+    }
+
+    #[test]
+    fn try_into_u128() {
+        assert_eq!(u128::try_from(&T::from_u128(1337)), Ok(1337));
+        assert_eq!(u128::try_from(&T::zero()), Ok(0));
+        assert_eq!(
+            u128::try_from(&CheckedCryptoBigInt::<L>::from_u128(u128::MAX)),
+            Ok(u128::MAX)
+        );
+        let too_large = T::from_u128(u128::MAX).add(&T::one());
+        assert_eq!(u128::try_from(&too_large), Err(U128Overflow));
+    }
+
+    #[test]
+    fn saturating_sub() {
+        fn simple_tester<U: BigIntTrait>() {
+            let (a, b) = (U::from_u128(62), U::from_u128(27));
+            assert_eq!(a.saturating_sub(&b), U::from_u128(35));
+            assert_eq!(a.saturating_sub(&a), U::zero());
+            assert_eq!(b.saturating_sub(&a), U::zero());
+        }
+
+        simple_tester::<BigInt>();
+        simple_tester::<T>();
+        simple_tester::<CheckedCryptoBigInt<L>>();
+    }
+
+    #[test]
+    fn neg_mod() {
+        fn simple_tester<U: BigIntTrait>() {
+            let m = U::from_u128(7919);
+            assert_eq!(U::zero().neg_mod(&m), U::zero());
+            assert_eq!(U::one().neg_mod(&m), U::from_u128(7918));
+            assert_eq!(U::from_u128(7919 + 5).neg_mod(&m), U::from_u128(7914));
+            assert_eq!(U::from_u128(7919 * 3).neg_mod(&m), U::zero());
+        }
+
+        simple_tester::<BigInt>();
+        simple_tester::<T>();
+        simple_tester::<CheckedCryptoBigInt<L>>();
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        fn simple_tester<U: BigIntTrait>(n: u128) {
+            let big_n = U::from_u128(n);
+            let bytes = big_n.to_be_bytes();
+            assert_eq!(bytes, n.to_be_bytes()[(n.leading_zeros() / 8) as usize..]);
+            assert_eq!(U::from_be_bytes(&bytes), Some(big_n));
+        }
+
+        simple_tester::<BigInt>(0);
+        simple_tester::<BigInt>(7919);
+        simple_tester::<T>(0);
+        simple_tester::<T>(0x1234_5678_9abc_def0_1122_3344_5566_7788);
+        simple_tester::<CheckedCryptoBigInt<L>>(u128::MAX);
+        let max = CheckedCryptoBigInt::<{ limbs_for_bits(128) }>::from_u128(u128::MAX);
+        let overflowed = max.add(&BigIntTrait::one());
+        assert_eq!(overflowed.to_be_bytes(), Vec::<u8>::new());
+        assert_eq!(u128::try_from(&overflowed), Err(U128Overflow));
+        assert_eq!(W128::from_be_bytes(&[1; 17]), None);
+    }
+
     #[test]
     fn test_pow() {
         fn simple_tester(a: u128, b: u128) {
             let big_a = T::from_u128(a);
             assert_eq!(big_a.pow(b), T::from_u128(a.pow(b as u32)));
+            assert_eq!(big_a.checked_pow(b as u32), Some(big_a.pow(b)));
         }
+        assert_eq!(T::from_u128(5).pow(3), T::from_u128(125));
         simple_tester(12, 4);
         simple_tester(3, 0);
         simple_tester(12, 6);
         simple_tester(12, 9);
     }
+
+    #[test]
+    fn checked_pow_overflow() {
+        assert_eq!(
+            W128::from_u128(2).checked_pow(127),
+            Some(W128::from_u128(1 << 127))
+        );
+        assert_eq!(W128::from_u128(2).checked_pow(128), None);
+        assert_eq!(
+            W128::from_u128(u128::MAX).checked_pow(1),
+            Some(W128::from_u128(u128::MAX))
+        );
+        assert_eq!(W128::from_u128(u128::MAX).checked_pow(0), Some(W128::one()));
+    }
+
+    #[test]
+    fn gcd_inv() {
+        fn simple_tester<U: BigIntTrait>() {
+            let n = U::from_u128;
+            let (gcd, inverse) = U::gcd_inv(&n(37), &n(7919));
+            assert_eq!(gcd, n(1));
+            let inverse = inverse.unwrap();
+            assert_eq!(n(37).mul(&inverse).rem(&n(7919)), n(1));
+            assert_eq!(U::gcd_inv(&n(7919 + 37), &n(7919)).1, Some(inverse));
+            assert_eq!(U::gcd_inv(&n(12), &n(18)), (n(6), None));
+            assert_eq!(U::gcd_inv(&n(0), &n(18)), (n(18), None));
+            // without an inverse, gcd_ext still returns a Bezout coefficient
+            let (gcd, y) = U::gcd_ext(&n(12), &n(18));
+            assert_eq!(gcd, n(6));
+            assert_eq!(n(12).mul(&y).rem(&n(18)), n(6));
+        }
+
+        simple_tester::<BigInt>();
+        simple_tester::<T>();
+        simple_tester::<CheckedCryptoBigInt<L>>();
+    }
+
+    #[test]
+    fn cmp_mod() {
+        fn simple_tester<U: BigIntTrait>() {
+            let (n, m) = (U::from_u128, U::from_u128(7919));
+            assert_eq!(n(7919 + 5).cmp_mod(&n(5), &m), Ordering::Equal);
+            assert_eq!(n(3 * 7919).cmp_mod(&n(0), &m), Ordering::Equal);
+            // 7919 + 5 is larger than 7 but its residue is smaller
+            assert_eq!(n(7919 + 5).cmp_mod(&n(7), &m), Ordering::Less);
+            assert_eq!(n(7).cmp_mod(&n(7919 + 5), &m), Ordering::Greater);
+        }
+
+        simple_tester::<BigInt>();
+        simple_tester::<T>();
+        simple_tester::<CheckedCryptoBigInt<L>>();
+        let m = BigInt::from(7919);
+        assert_eq!(
+            BigInt::from(-1).cmp_mod(&BigInt::from(7918), &m),
+            Ordering::Equal
+        );
+    }
+
+    #[test]
+    fn to_naf() {
+        fn simple_tester<U: BigIntTrait>(n: u128, window: usize) {
+            let naf = U::from_u128(n).to_naf(window);
+            let value = naf
+                .iter()
+                .rev()
+                .fold(0_i128, |value, d| 2 * value + *d as i128);
+            assert_eq!(value, n as i128);
+            for (i, d) in naf.iter().enumerate().filter(|(_, d)| **d != 0) {
+                assert!(d % 2 != 0 && (d.unsigned_abs() as u32) < 1 << (window - 1));
+                assert!(naf[i + 1..].iter().take(window - 1).all(|d| *d == 0));
+            }
+        }
+
+        assert_eq!(T::from_u128(7).to_naf(2), [-1, 0, 0, 1]);
+        assert_eq!(T::zero().to_naf(4), []);
+        for window in 2..=8 {
+            for n in [1, 7, 255, 7919, 0xdead_beef, u64::MAX as u128] {
+                simple_tester::<BigInt>(n, window);
+                simple_tester::<T>(n, window);
+                simple_tester::<CheckedCryptoBigInt<L>>(n, window);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot compute the NAF")]
+    fn to_naf_window_too_large() {
+        T::from_u128(7).to_naf(9);
+    }
+
+    #[test]
+    fn random_coprime_to() {
+        fn simple_tester<U: BigIntTrait>() {
+            // 7919*37*2: about half of the candidates are even, hence rejected
+            let modulus = U::from_u128(7919 * 37 * 2);
+            for _ in 0..100 {
+                let n = U::random_coprime_to(&modulus);
+                assert!(n < modulus);
+                assert_eq!(n.gcd(&modulus), U::one());
+            }
+            assert_eq!(U::random_coprime_to(&U::one()), U::zero());
+        }
+
+        simple_tester::<BigInt>();
+        simple_tester::<W256>();
+        simple_tester::<CheckedCryptoBigInt<{ limbs_for_bits(256) }>>();
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot sample an integer coprime")]
+    fn random_coprime_to_zero() {
+        T::random_coprime_to(&T::zero());
+    }
+
+    #[test]
+    fn bounded_fits() {
+        assert_eq!(W128::MAX_BITS, 128);
+        assert!(W128::fits(0));
+        assert!(W128::fits(128));
+        assert!(!W128::fits(129));
+        assert_eq!(
+            CheckedCryptoBigInt::<{ limbs_for_bits(256) }>::MAX_BITS,
+            256
+        );
+        assert!(CheckedCryptoBigInt::<{ limbs_for_bits(256) }>::fits(256));
+        assert!(!CheckedCryptoBigInt::<{ limbs_for_bits(256) }>::fits(257));
+        assert!(BigInt::fits(usize::MAX));
+    }
+
+    #[test]
+    fn reduce_once() {
+        fn simple_tester<U: BigIntTrait>() {
+            let (n, m) = (U::from_u128, U::from_u128(7919));
+            assert_eq!(n(0).reduce_once(&m), n(0));
+            assert_eq!(n(7918).reduce_once(&m), n(7918));
+            assert_eq!(n(7919).reduce_once(&m), n(0));
+            assert_eq!(n(7920).reduce_once(&m), n(1));
+            assert_eq!(n(2 * 7919 - 1).reduce_once(&m), n(7918));
+        }
+
+        simple_tester::<BigInt>();
+        simple_tester::<T>();
+        simple_tester::<CheckedCryptoBigInt<L>>();
+    }
+
+    #[test]
+    #[cfg(feature = "rug")]
+    #[should_panic]
+    fn rug_random_mod_zero() {
+        rug::Integer::random_mod(&rug::Integer::from(0));
+    }
+
+    #[test]
+    #[cfg(feature = "rug")]
+    #[should_panic]
+    fn rug_generate_zero_bit_prime() {
+        rug::Integer::generate_prime_with_rng(Some(0), &mut rand::thread_rng());
+    }
+
+    #[test]
+    #[cfg(feature = "rug")]
+    fn rug_matches_other_backends() {
+        fn same<U: BigIntTrait>(a: &rug::Integer, b: &U) {
+            assert_eq!(a.to_be_bytes(), b.to_be_bytes(), "{} != {}", a, b);
+        }
+
+        // crypto-bigint is the default backend, num_bigint the other unbounded one
+        fn simple_tester<U: BigIntTrait>() {
+            let vectors = [1, 2, 7919, 0xdead_beef, u64::MAX as u128, u128::MAX];
+            for &a in &vectors {
+                let (rug_a, u_a) = (rug::Integer::from_u128(a), U::from_u128(a));
+                same(&BigIntTrait::sqrt(&rug_a), &u_a.sqrt());
+                same(&BigIntTrait::pow(&rug_a, 3), &u_a.pow(3));
+                assert_eq!(BigIntTrait::to_naf(&rug_a, 4), u_a.to_naf(4));
+                assert_eq!(
+                    BigIntTrait::is_probably_prime(&rug_a),
+                    u_a.is_probably_prime()
+                );
+                for &b in &vectors {
+                    let (rug_b, u_b) = (rug::Integer::from_u128(b), U::from_u128(b));
+                    same(&BigIntTrait::add(&rug_a, &rug_b), &u_a.add(&u_b));
+                    same(&BigIntTrait::mul(&rug_a, &rug_b), &u_a.mul(&u_b));
+                    same(&BigIntTrait::div(&rug_a, &rug_b), &u_a.div(&u_b));
+                    same(&BigIntTrait::rem(&rug_a, &rug_b), &u_a.rem(&u_b));
+                    same(&BigIntTrait::gcd(&rug_a, &rug_b), &u_a.gcd(&u_b));
+                    same(
+                        &BigIntTrait::saturating_sub(&rug_b, &rug_a),
+                        &u_b.saturating_sub(&u_a),
+                    );
+                    let (rug_gcd, rug_inverse) = rug::Integer::gcd_inv(&rug_a, &rug_b);
+                    let (u_gcd, u_inverse) = U::gcd_inv(&u_a, &u_b);
+                    same(&rug_gcd, &u_gcd);
+                    assert_eq!(rug_inverse.is_some(), u_inverse.is_some());
+                    if let (Some(rug_inverse), Some(u_inverse)) = (rug_inverse, u_inverse) {
+                        same(&rug_inverse, &u_inverse);
+                    }
+                }
+            }
+        }
+
+        simple_tester::<T>();
+        simple_tester::<BigInt>();
+
+        let modulus = rug::Integer::from_u128(7919);
+        for _ in 0..100 {
+            assert!(rug::Integer::random_mod(&modulus) < modulus);
+        }
+        assert!(BigIntTrait::is_probably_prime(
+            &rug::Integer::generate_prime(Some(64))
+        ));
+    }
 }
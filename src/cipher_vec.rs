@@ -0,0 +1,141 @@
+use super::{
+    hensel_code::HenselCode,
+    ops::{Add, Mul},
+};
+use crate::bigint::BigIntTrait;
+
+/// A vector of ciphertexts, on which homomorphic operations are performed element-wise
+#[derive(Clone, Debug)]
+pub struct CipherVec<T: BigIntTrait>(pub Vec<HenselCode<T>>);
+
+impl<T: BigIntTrait> CipherVec<T> {
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Computes the dot product of two CipherVecs of the same (non-zero) length
+    pub fn dot(&self, other: &CipherVec<T>) -> HenselCode<T> {
+        let mut products = (self * other).0.into_iter();
+        let first = match products.next() {
+            Some(product) => product,
+            None => panic!("cannot compute the dot product of empty vectors"),
+        };
+        products.fold(first, |acc, product| acc + product)
+    }
+}
+
+/// Adds two &CipherVecs element-wise
+impl<'b, T: BigIntTrait> Add<&'b CipherVec<T>> for &CipherVec<T> {
+    type Output = CipherVec<T>;
+    fn add(self, other: &'b CipherVec<T>) -> CipherVec<T> {
+        if self.len() != other.len() {
+            panic!(
+                "cannot add vectors of lengths {} and {}",
+                self.len(),
+                other.len()
+            );
+        }
+        CipherVec(self.0.iter().zip(&other.0).map(|(a, b)| a + b).collect())
+    }
+}
+
+/// Multiplies two &CipherVecs element-wise
+impl<'b, T: BigIntTrait> Mul<&'b CipherVec<T>> for &CipherVec<T> {
+    type Output = CipherVec<T>;
+    fn mul(self, other: &'b CipherVec<T>) -> CipherVec<T> {
+        if self.len() != other.len() {
+            panic!(
+                "cannot multiply vectors of lengths {} and {}",
+                self.len(),
+                other.len()
+            );
+        }
+        CipherVec(self.0.iter().zip(&other.0).map(|(a, b)| a * b).collect())
+    }
+}
+
+/// Adds two CipherVecs element-wise
+impl<T: BigIntTrait> Add<CipherVec<T>> for CipherVec<T> {
+    type Output = CipherVec<T>;
+    fn add(self, other: CipherVec<T>) -> CipherVec<T> {
+        &self + &other
+    }
+}
+
+/// Multiplies two CipherVecs element-wise
+impl<T: BigIntTrait> Mul<CipherVec<T>> for CipherVec<T> {
+    type Output = CipherVec<T>;
+    fn mul(self, other: CipherVec<T>) -> CipherVec<T> {
+        &self * &other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bigint::BigIntTrait;
+    use crate::crypto_parameters::{EncryptionScheme, PrivateKeySchemeCryptographicParameters};
+    use crate::rational::Rational;
+
+    use num_bigint_dig::BigInt;
+
+    type T = BigInt;
+
+    fn crypto_params() -> PrivateKeySchemeCryptographicParameters<T> {
+        PrivateKeySchemeCryptographicParameters::<T>::new(
+            T::from_u128(7919),
+            T::from_u128(37),
+            T::from_u128(41),
+            T::from_u128(5897),
+            T::from_u128(7759),
+        )
+    }
+
+    fn rational(num: u128, denom: u128) -> Rational<T> {
+        Rational::<T> {
+            num: T::from_u128(num),
+            denom: T::from_u128(denom),
+        }
+    }
+
+    #[test]
+    fn element_wise_ops() {
+        let crypto_params = crypto_params();
+        let v1 = vec![rational(1, 2), rational(2, 3)];
+        let v2 = vec![rational(3, 1), rational(1, 4)];
+        let (c1, c2) = (
+            crypto_params.encrypt_vec(&v1),
+            crypto_params.encrypt_vec(&v2),
+        );
+
+        let sum = crypto_params.decrypt_vec(&c1 + &c2);
+        assert_eq!(sum, vec![rational(7, 2), rational(11, 12)]);
+        let product = crypto_params.decrypt_vec(c1 * c2);
+        assert_eq!(product, vec![rational(3, 2), rational(1, 6)]);
+    }
+
+    #[test]
+    fn dot_product() {
+        let crypto_params = crypto_params();
+        let v1 = vec![rational(1, 2), rational(2, 3)];
+        let v2 = vec![rational(3, 1), rational(1, 4)];
+        let (c1, c2) = (
+            crypto_params.encrypt_vec(&v1),
+            crypto_params.encrypt_vec(&v2),
+        );
+
+        assert_eq!(crypto_params.decrypt(c1.dot(&c2)), rational(5, 3));
+    }
+
+    #[test]
+    #[should_panic]
+    fn mismatched_lengths() {
+        let crypto_params = crypto_params();
+        let c1 = crypto_params.encrypt_vec(&[rational(1, 2)]);
+        let c2 = crypto_params.encrypt_vec(&[rational(1, 2), rational(2, 3)]);
+        let _ = c1 + c2;
+    }
+}
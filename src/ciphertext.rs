@@ -0,0 +1,67 @@
+use super::{hensel_code::HenselCode, ops::Add};
+use crate::bigint::BigIntTrait;
+
+/// A ciphertext tracking its multiplicative depth, i.e. the largest number of multiplications
+/// chained since it was encrypted, to compare against the `max_depth` of the key
+#[derive(Clone, Debug)]
+pub struct Ciphertext<T: BigIntTrait> {
+    pub hc: HenselCode<T>,
+    pub mul_depth: usize,
+}
+
+impl<T: BigIntTrait> Ciphertext<T> {
+    /// Wraps a fresh ciphertext, of depth zero
+    pub fn new(hc: HenselCode<T>) -> Self {
+        Ciphertext { hc, mul_depth: 0 }
+    }
+
+    /// Multiplies two ciphertexts, one level deeper than the deepest of them
+    pub fn mul_ciphertexts(&self, other: &Ciphertext<T>) -> Ciphertext<T> {
+        Ciphertext {
+            hc: &self.hc * &other.hc,
+            mul_depth: self.mul_depth.max(other.mul_depth) + 1,
+        }
+    }
+}
+
+impl<T: BigIntTrait> From<HenselCode<T>> for Ciphertext<T> {
+    fn from(hc: HenselCode<T>) -> Self {
+        Ciphertext::new(hc)
+    }
+}
+
+/// Adds two &Ciphertexts, keeping the depth of the deepest of them
+impl<'b, T: BigIntTrait> Add<&'b Ciphertext<T>> for &Ciphertext<T> {
+    type Output = Ciphertext<T>;
+    fn add(self, other: &'b Ciphertext<T>) -> Ciphertext<T> {
+        Ciphertext {
+            hc: &self.hc + &other.hc,
+            mul_depth: self.mul_depth.max(other.mul_depth),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Ciphertext;
+    use crate::bigint::BigIntTrait;
+    use crate::hensel_code::new_hensel_code;
+
+    type T = crate::bigint::WrappingCryptoBigInt;
+
+    #[test]
+    fn depth_increments_on_mul() {
+        let p = T::from_u128(7919);
+        let x = Ciphertext::new(new_hensel_code(&p, &T::from_u128(3)));
+        let y = Ciphertext::from(new_hensel_code(&p, &T::from_u128(5)));
+        assert_eq!(x.mul_depth, 0);
+        let xy = x.mul_ciphertexts(&y);
+        assert_eq!(xy.mul_depth, 1);
+        assert_eq!(xy.hc.res, T::from_u128(15));
+        assert_eq!((&xy + &x).mul_depth, 1);
+        let xyxy = xy.mul_ciphertexts(&xy);
+        assert_eq!(xyxy.mul_depth, 2);
+        assert_eq!(xyxy.mul_ciphertexts(&x).mul_depth, 3);
+        assert_eq!((&xyxy + &xy).hc.res, T::from_u128(225 + 15));
+    }
+}
@@ -0,0 +1,92 @@
+use super::{
+    hensel_code::HenselCode,
+    ops::{Add, Mul},
+};
+use crate::bigint::BigIntTrait;
+
+/// An arithmetic circuit over ciphertexts, as a tree of homomorphic operations whose leaves are
+/// indices into the inputs
+#[derive(Clone, Debug)]
+pub enum Circuit<T: BigIntTrait> {
+    Input(usize),
+    Add(Box<Circuit<T>>, Box<Circuit<T>>),
+    Mul(Box<Circuit<T>>, Box<Circuit<T>>),
+    /// multiplies a subcircuit by a plain integer
+    ScalarMul(Box<Circuit<T>>, T),
+}
+
+impl<T: BigIntTrait> Circuit<T> {
+    pub fn input(index: usize) -> Self {
+        Circuit::Input(index)
+    }
+
+    pub fn scalar_mul(a: Circuit<T>, k: T) -> Self {
+        Circuit::ScalarMul(Box::new(a), k)
+    }
+
+    /// Evaluates the circuit on the ciphertexts `inputs`, panics if an input index is out of
+    /// range
+    pub fn evaluate(&self, inputs: &[HenselCode<T>]) -> HenselCode<T> {
+        match self {
+            Circuit::Input(index) => match inputs.get(*index) {
+                Some(input) => input.clone(),
+                None => panic!(
+                    "cannot evaluate input {} of a circuit with {} inputs",
+                    index,
+                    inputs.len()
+                ),
+            },
+            Circuit::Add(a, b) => &a.evaluate(inputs) + &b.evaluate(inputs),
+            Circuit::Mul(a, b) => &a.evaluate(inputs) * &b.evaluate(inputs),
+            Circuit::ScalarMul(a, k) => a.evaluate(inputs).mul_scalar(k),
+        }
+    }
+}
+
+/// Adds the outputs of two Circuits
+impl<T: BigIntTrait> Add<Circuit<T>> for Circuit<T> {
+    type Output = Circuit<T>;
+    fn add(self, other: Circuit<T>) -> Circuit<T> {
+        Circuit::Add(Box::new(self), Box::new(other))
+    }
+}
+
+/// Multiplies the outputs of two Circuits
+impl<T: BigIntTrait> Mul<Circuit<T>> for Circuit<T> {
+    type Output = Circuit<T>;
+    fn mul(self, other: Circuit<T>) -> Circuit<T> {
+        Circuit::Mul(Box::new(self), Box::new(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Circuit;
+    use crate::bigint::BigIntTrait;
+    use crate::hensel_code::new_hensel_code;
+
+    type T = crate::bigint::WrappingCryptoBigInt;
+
+    #[test]
+    fn evaluate_plain_residues() {
+        let p = T::from_u128(7919);
+        let inputs: Vec<_> = [3, 5, 7000]
+            .into_iter()
+            .map(|n| new_hensel_code(&p, &T::from_u128(n)))
+            .collect();
+        // 2*(x + y)*z = 2*8*7000 (mod 7919)
+        let (x, y, z) = (Circuit::input(0), Circuit::input(1), Circuit::input(2));
+        let circuit = Circuit::scalar_mul((x + y) * z, T::from_u128(2));
+        assert_eq!(
+            circuit.evaluate(&inputs).res,
+            T::from_u128(2 * 8 * 7000 % 7919)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot evaluate input 3")]
+    fn evaluate_missing_input() {
+        let inputs = [new_hensel_code(&T::from_u128(7919), &T::one())];
+        (Circuit::input(0) + Circuit::input(3)).evaluate(&inputs);
+    }
+}
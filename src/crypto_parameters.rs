@@ -1,12 +1,107 @@
 extern crate crypto_bigint;
 
 use crate::{
-    bigint::BigIntTrait,
-    hensel_code::{chinese_remainder, new_hensel_code, HenselCode},
+    bigint::{are_pairwise_coprime, BigIntTrait, Bounded},
+    cipher_vec::CipherVec,
+    circuit::Circuit,
+    hensel_code::{
+        checked_chinese_remainder, chinese_remainder, encode, new_hensel_code, CodingError, Decode,
+        Encode, HenselCode,
+    },
     rational::Rational,
 };
 
-//use std::convert::From;
+use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+
+use std::{convert::TryFrom, fmt};
+
+/// Error returned when building cryptographic parameters from invalid input
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum ParamError {
+    WrongNumberOfPrimes { expected: usize, found: usize },
+    InvalidNumber(String),
+    InvalidPrime(String),
+    InvalidPrimeIndices(Vec<usize>),
+    ProductOverflow,
+    NotEnoughPrimes(usize),
+    NotPairwiseCoprime,
+}
+
+impl fmt::Display for ParamError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParamError::WrongNumberOfPrimes { expected, found } => {
+                write!(f, "expected {} primes, found {}", expected, found)
+            }
+            ParamError::InvalidNumber(s) => write!(f, "cannot parse '{}' as a number", s),
+            ParamError::InvalidPrime(s) => write!(f, "'{}' is not a prime", s),
+            ParamError::InvalidPrimeIndices(indices) => {
+                write!(f, "{:?} are not distinct indices of the 5 primes", indices)
+            }
+            ParamError::ProductOverflow => {
+                write!(
+                    f,
+                    "the product of the primes does not fit in the big int type"
+                )
+            }
+            ParamError::NotEnoughPrimes(bits) => {
+                write!(f, "cannot find enough distinct {}-bit primes", bits)
+            }
+            ParamError::NotPairwiseCoprime => write!(f, "the primes are not pairwise coprime"),
+        }
+    }
+}
+
+impl std::error::Error for ParamError {}
+
+/// Error returned when a message cannot be encrypted
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum EncryptError {
+    /// the plaintext modulus is larger than the prime `p1` used for decryption
+    PlaintextModulusTooLarge,
+    /// the message does not fit in the Farey bound of the plaintext modulus
+    MessageOutOfBounds,
+}
+
+impl fmt::Display for EncryptError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EncryptError::PlaintextModulusTooLarge => {
+                write!(f, "plaintext modulus is larger than p1")
+            }
+            EncryptError::MessageOutOfBounds => {
+                write!(f, "message does not fit in the plaintext modulus")
+            }
+        }
+    }
+}
+
+impl std::error::Error for EncryptError {}
+
+/// Error returned when a ciphertext does not decrypt to a message within the reconstruction
+/// bounds
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum DecryptError {
+    /// the residue modulo `p4` does not reconstruct to a rational
+    NoReconstruction,
+    /// the reconstructed rational exceeds the Farey bound of `p1` or `p4`
+    OutOfBounds,
+}
+
+impl fmt::Display for DecryptError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DecryptError::NoReconstruction => {
+                write!(f, "ciphertext does not reconstruct to a rational modulo p4")
+            }
+            DecryptError::OutOfBounds => {
+                write!(f, "decrypted message exceeds the reconstruction bounds")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DecryptError {}
 
 /// This is a private key, with five private parameters.
 /// Rust doesn't like "const generics expressions" so it is needed to assume that
@@ -17,114 +112,692 @@ pub struct PrivateKeySchemeCryptographicParameters<T: BigIntTrait> {
     _p3: T,
     _p4: T,
     _p5: T,
+    g: T, // the product p1*...*p5, modulus of the ciphertexts
 }
 
 pub trait EncryptionScheme<T: BigIntTrait> {
     fn encrypt(&self, m: Rational<T>) -> HenselCode<T>;
     fn decrypt(&self, hc: HenselCode<T>) -> Rational<T>;
+
+    /// Encrypts each message of a slice into a CipherVec
+    fn encrypt_vec(&self, messages: &[Rational<T>]) -> CipherVec<T> {
+        CipherVec(messages.iter().map(|m| self.encrypt(m.clone())).collect())
+    }
+
+    /// Decrypts each ciphertext of a CipherVec
+    fn decrypt_vec(&self, cv: CipherVec<T>) -> Vec<Rational<T>> {
+        cv.0.into_iter().map(|hc| self.decrypt(hc)).collect()
+    }
+
+    /// Evaluates `circuit` homomorphically on the ciphertexts `inputs`
+    fn evaluate(&self, circuit: &Circuit<T>, inputs: &[HenselCode<T>]) -> HenselCode<T> {
+        circuit.evaluate(inputs)
+    }
+
+    /// Decrypts `hc` and converts the message to the nearest `f64`
+    fn decrypt_to_f64(&self, hc: HenselCode<T>) -> f64 {
+        self.decrypt(hc).to_f64()
+    }
+
+    /// Checks that `hc` decrypts to `expected`, comparing the rationals as fractions
+    fn verify(&self, hc: &HenselCode<T>, expected: &Rational<T>) -> bool {
+        self.decrypt(hc.clone()) == *expected
+    }
+}
+
+/// Number of primes of a given size drawn by `generate_primes` before giving up on finding one
+/// which differs from the primes generated already
+const MAX_PRIME_ATTEMPTS: usize = 100;
+
+/// Configuration of the private key generation
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct KeyGenConfig {
+    /// bit sizes of the primes `p1, ..., p5`
+    pub prime_bits: [usize; 5],
+    /// seed of the ChaCha20 CSPRNG generating the primes, or `None` to use the thread RNG
+    pub seed: Option<u64>,
+}
+
+impl KeyGenConfig {
+    /// Derives the bit sizes of the primes from the security parameters `lambda, d`
+    pub fn from_security(lambda: u32, d: u32) -> Self {
+        let rho = lambda;
+        let eta = 2 * (d + 2) * lambda;
+        let gamma: u32 = (lambda / lambda.ilog2()) * (eta - rho).pow(2);
+        let mu = gamma - eta - 2 * lambda;
+        KeyGenConfig {
+            prime_bits: [(rho + 1), (rho / 2), (rho / 2), eta, mu].map(|size| size as usize),
+            seed: None,
+        }
+    }
+}
+
+/// Defaults to the security parameters `lambda = 8, d = 1`, which are only suited to tests
+impl Default for KeyGenConfig {
+    fn default() -> Self {
+        Self::from_security(8, 1)
+    }
+}
+
+/// The noise sampled for a fresh ciphertext: `s1`, `s2`, `s3` modulo `p1`, `p2`, `p3`, and
+/// `delta` modulo `delta_max = p1*p2*p3*p5`. Encryption currently enforces a null noise, so these
+/// values are sampled but never reach the ciphertext.
+#[derive(Clone, Debug)]
+pub struct NoiseReport<T: BigIntTrait> {
+    pub s1: T,
+    pub s2: T,
+    pub s3: T,
+    pub delta: T,
+    pub delta_max: T,
+}
+
+/// Diagnostic summary of a ciphertext, returned by `inspect`
+#[derive(Clone, Debug)]
+pub struct CiphertextStats<T: BigIntTrait> {
+    /// the residue modulo each of the primes `p1, ..., p5`
+    pub residues: Vec<T>,
+    /// the estimated noise, as used by `remaining_capacity`
+    pub noise: T,
+    /// whether `try_decrypt` succeeds
+    pub decryptable: bool,
+}
+
+/// The reconstruction bounds of the decryption primes. `decrypt` reconstructs the message modulo
+/// `p4` and then modulo `p1`, so it is only recovered when its numerator and denominator are
+/// within the Farey bounds of both primes.
+#[derive(Clone, Debug)]
+pub struct DecryptContext<T: BigIntTrait> {
+    p1: T,
+    p4: T,
+    /// the Farey bound of `p1`
+    pub bound_p1: T,
+    /// the Farey bound of `p4`
+    pub bound_p4: T,
+}
+
+impl<T: BigIntTrait> DecryptContext<T> {
+    pub fn new(p1: &T, p4: &T) -> Self {
+        DecryptContext {
+            bound_p1: T::farey_bound(p1),
+            bound_p4: T::farey_bound(p4),
+            p1: p1.clone(),
+            p4: p4.clone(),
+        }
+    }
+
+    /// Returns the bound on the numerator and denominator of a decryptable message, i.e. the
+    /// smaller of the two Farey bounds
+    pub fn bound(&self) -> &T {
+        if self.bound_p1 < self.bound_p4 {
+            &self.bound_p1
+        } else {
+            &self.bound_p4
+        }
+    }
+
+    /// Decrypts `hc`, checking that the message reconstructed modulo `p4` is within `bound` and
+    /// is therefore recovered modulo `p1` too. A message exceeding the bounds may still wrap
+    /// around to a rational within them, which cannot be detected. As in `decrypt`, there is no
+    /// early return on the secret message: the reconstruction, the re-encoding check and the
+    /// bound check always all run, and their outcomes are only branched on once at the end.
+    pub fn try_decrypt(&self, hc: &HenselCode<T>) -> Result<Rational<T>, DecryptError> {
+        let hc_p4 = hc.change_modulus(&self.p4);
+        let r_p4 = Rational::<T>::from(&hc_p4).reduce();
+        // same check as `decode`, with non-short-circuiting `&` instead of early returns
+        let reconstructed = (self.p4.gcd(&r_p4.denom) == T::one())
+            & (HenselCode::<T>::from((&self.p4, &r_p4)).res == hc_p4.res);
+        // the reconstructed numerator is non-negative
+        let in_bounds = (r_p4.num <= *self.bound()) & (r_p4.denom <= *self.bound());
+        let m = Rational::<T>::from(&HenselCode::<T>::from((&self.p1, &r_p4)));
+
+        // 0: decrypted, 1: out of bounds, 2: no reconstruction
+        let status = T::conditional_select(
+            &T::from_u128(2),
+            &T::conditional_select(&T::one(), &T::zero(), in_bounds),
+            reconstructed,
+        );
+        if status.is_zero() {
+            Ok(m)
+        } else if status == T::one() {
+            Err(DecryptError::OutOfBounds)
+        } else {
+            Err(DecryptError::NoReconstruction)
+        }
+    }
 }
 
 impl<T: BigIntTrait> PrivateKeySchemeCryptographicParameters<T> {
+    /// Panics if the ciphertext modulus `g = p1*...*p5` does not fit in `T`, as it would silently
+    /// wrap for fixed-size big ints
     pub fn new(_p1: T, _p2: T, _p3: T, _p4: T, _p5: T) -> Self {
+        let g = match checked_prime_product(&[&_p1, &_p2, &_p3, &_p4, &_p5]) {
+            Some(g) => g,
+            None => panic!(
+                "the product of the primes {}, {}, {}, {}, {} does not fit in the big int type",
+                _p1, _p2, _p3, _p4, _p5
+            ),
+        };
         Self {
             _p1,
             _p2,
             _p3,
             _p4,
             _p5,
+            g,
         }
     }
 
-    /// generates 5 distincts primes from security parameters `lambda, d`
+    /// Same as `new`, but first rejects parameters that are obviously not primes: values below 2
+    /// and even values other than 2, as well as primes that are not pairwise coprime
+    pub fn try_new(_p1: T, _p2: T, _p3: T, _p4: T, _p5: T) -> Result<Self, ParamError> {
+        let two = T::from_u128(2);
+        for p in [&_p1, &_p2, &_p3, &_p4, &_p5] {
+            if *p < two || (*p != two && p.rem(&two).is_zero()) {
+                return Err(ParamError::InvalidPrime(p.to_string()));
+            }
+        }
+        let primes = [
+            _p1.clone(),
+            _p2.clone(),
+            _p3.clone(),
+            _p4.clone(),
+            _p5.clone(),
+        ];
+        if !are_pairwise_coprime(&primes) {
+            return Err(ParamError::NotPairwiseCoprime);
+        }
+        if checked_prime_product(&[&_p1, &_p2, &_p3, &_p4, &_p5]).is_none() {
+            return Err(ParamError::ProductOverflow);
+        }
+        Ok(Self::new(_p1, _p2, _p3, _p4, _p5))
+    }
+
+    /// Same as `try_new`, taking the five primes `[p1, p2, p3, p4, p5]` as a slice
+    pub fn from_primes(primes: &[T]) -> Result<Self, ParamError> {
+        match primes {
+            [p1, p2, p3, p4, p5] => {
+                Self::try_new(p1.clone(), p2.clone(), p3.clone(), p4.clone(), p5.clone())
+            }
+            _ => Err(ParamError::WrongNumberOfPrimes {
+                expected: 5,
+                found: primes.len(),
+            }),
+        }
+    }
+
+    /// Returns the ciphertext modulus `g = p1*...*p5`, computed once at construction
+    pub fn prime_product(&self) -> &T {
+        &self.g
+    }
+
+    /// Returns an iterator over the primes `p1, ..., p5`
+    pub fn primes(&self) -> impl Iterator<Item = &T> {
+        [&self._p1, &self._p2, &self._p3, &self._p4, &self._p5].into_iter()
+    }
+
+    /// generates 5 distincts primes from security parameters `lambda, d`. Panics if there are
+    /// not enough primes of the derived bit sizes.
     pub fn new_from_params(lambda: u32, d: u32) -> Self {
-        let rho = lambda;
-        let eta = 2 * (d + 2) * lambda;
-        let gamma: u32 = (lambda / lambda.ilog2()) * (eta - rho).pow(2);
-        let mu = gamma - eta - 2 * lambda;
+        let sizes = KeyGenConfig::from_security(lambda, d).prime_bits;
+        Self::generate_primes(&sizes, |size| T::generate_prime(Some(size)))
+            .unwrap_or_else(|e| panic!("cannot generate a key for lambda = {}: {}", lambda, e))
+    }
+
+    /// Same as `new_from_params`, deterministically generating the primes from `seed` with a
+    /// ChaCha20 CSPRNG, so that the same seed always yields the same key
+    pub fn generate_seeded(lambda: u32, d: u32, seed: u64) -> Self {
+        let sizes = KeyGenConfig::from_security(lambda, d).prime_bits;
+        let mut rng = ChaCha20Rng::seed_from_u64(seed);
+        Self::generate_primes(&sizes, |size| {
+            T::generate_prime_with_rng(Some(size), &mut rng)
+        })
+        .unwrap_or_else(|e| panic!("cannot generate a key for lambda = {}: {}", lambda, e))
+    }
+
+    /// Generates 5 distinct primes of the bit sizes given by `cfg`, seeded by `cfg.seed` if any
+    pub fn generate_with_config(cfg: &KeyGenConfig) -> Result<Self, ParamError> {
+        if let Some(bits) = cfg.prime_bits.iter().find(|bits| **bits < 2) {
+            return Err(ParamError::InvalidNumber(format!("{}-bit prime", bits)));
+        }
+        match cfg.seed {
+            Some(seed) => {
+                let mut rng = ChaCha20Rng::seed_from_u64(seed);
+                Self::generate_primes(&cfg.prime_bits, |size| {
+                    T::generate_prime_with_rng(Some(size), &mut rng)
+                })
+            }
+            None => Self::generate_primes(&cfg.prime_bits, |size| T::generate_prime(Some(size))),
+        }
+    }
+
+    /// Generates distinct primes of bit sizes `sizes` with `generate_prime`, giving up on a size
+    /// after `MAX_PRIME_ATTEMPTS` primes which were all generated already
+    fn generate_primes(
+        sizes: &[usize; 5],
+        mut generate_prime: impl FnMut(usize) -> T,
+    ) -> Result<Self, ParamError> {
         let mut primes: Vec<T> = Vec::new();
-        for size in [(rho + 1), (rho / 2), (rho / 2), eta, mu] {
-            loop {
-                let current_p = T::generate_prime(Some(size as usize));
-                if !primes.contains(&current_p) {
-                    primes.push(current_p);
-                    break;
-                }
+        for size in sizes {
+            let current_p = (0..MAX_PRIME_ATTEMPTS)
+                .map(|_| generate_prime(*size))
+                .find(|p| !primes.contains(p))
+                .ok_or(ParamError::NotEnoughPrimes(*size))?;
+            primes.push(current_p);
+        }
+        Self::from_primes(&primes)
+    }
+
+    /// Encrypts `m` after checking that its numerator and denominator are within the Farey bound
+    /// of `plaintext_modulus`, which must not exceed `p1`
+    pub fn encrypt_bounded(
+        &self,
+        m: Rational<T>,
+        plaintext_modulus: &T,
+    ) -> Result<HenselCode<T>, EncryptError> {
+        if *plaintext_modulus > self._p1 {
+            return Err(EncryptError::PlaintextModulusTooLarge);
+        }
+        if !fits_reconstruction_bound(&m, plaintext_modulus) {
+            return Err(EncryptError::MessageOutOfBounds);
+        }
+        Ok(self.encrypt(m))
+    }
+
+    /// Returns the primes at `indices`, which must be distinct and lower than 5
+    fn selected_primes(&self, indices: &[usize]) -> Result<Vec<&T>, ParamError> {
+        let primes: Vec<&T> = self.primes().collect();
+        let all_valid = indices
+            .iter()
+            .enumerate()
+            .all(|(i, index)| *index < primes.len() && !indices[..i].contains(index));
+        if indices.is_empty() || !all_valid {
+            return Err(ParamError::InvalidPrimeIndices(indices.to_vec()));
+        }
+        Ok(indices.iter().map(|index| primes[*index]).collect())
+    }
+
+    /// Encrypts `m` modulo the product of the primes at `indices` (e.g. `[0, 1, 2]` for
+    /// `p1*p2*p3`), leaving the others for later use. The first of these primes is the decryption
+    /// prime: as in `rerandomize`, the ciphertext is masked by a random multiple of it.
+    pub fn encrypt_with_primes(
+        &self,
+        m: Rational<T>,
+        indices: &[usize],
+    ) -> Result<HenselCode<T>, ParamError> {
+        let primes = self.selected_primes(indices)?;
+        let modulus = primes.iter().fold(T::one(), |acc, p| acc.mul(p));
+        // an encryption of zero modulo `modulus` which vanishes modulo the decryption prime
+        let delta_max = modulus.div(primes[0]);
+        let zero = new_hensel_code(&modulus, &T::random_mod(&delta_max).mul(primes[0]));
+        Ok(HenselCode::from((&modulus, &m)) + zero)
+    }
+
+    /// Decrypts a ciphertext of `encrypt_with_primes`, by reducing it modulo the decryption prime,
+    /// i.e. the first of the primes at `indices`
+    pub fn decrypt_with_primes(
+        &self,
+        hc: &HenselCode<T>,
+        indices: &[usize],
+    ) -> Result<Rational<T>, ParamError> {
+        let p = self.selected_primes(indices)?[0];
+        Ok(Rational::from(&hc.change_modulus(p)))
+    }
+
+    /// Number of bytes of data per ciphertext in `encrypt_bytes`: each chunk is encrypted as an
+    /// integer made of a marker byte followed by the data bytes, which must be within the Farey
+    /// bounds of `p1` and `p4`
+    pub fn bytes_per_chunk(&self) -> usize {
+        let (n_max1, n_max4) = (T::farey_bound(&self._p1), T::farey_bound(&self._p4));
+        let n_max = if n_max1 < n_max4 { n_max1 } else { n_max4 };
+        // 256^k <= n_max + 1 where k is the length of n_max + 1 minus one byte
+        let capacity = n_max.add(&T::one()).to_be_bytes().len().saturating_sub(1);
+        capacity.saturating_sub(1)
+    }
+
+    /// Splits `data` in chunks of `bytes_per_chunk` bytes (the last one may be shorter), and
+    /// encrypts each of them as an integer. Panics if the primes are too small to hold a byte.
+    pub fn encrypt_bytes(&self, data: &[u8]) -> Vec<HenselCode<T>> {
+        let chunk_size = self.bytes_per_chunk();
+        if chunk_size == 0 {
+            panic!(
+                "cannot encrypt bytes with the primes p1 = {} and p4 = {}",
+                self._p1, self._p4
+            );
+        }
+        data.chunks(chunk_size)
+            .map(|chunk| {
+                let marked: Vec<u8> = [1].iter().chain(chunk).copied().collect();
+                let n = T::from_be_bytes(&marked).unwrap();
+                self.encrypt(Rational::from(n))
+            })
+            .collect()
+    }
+
+    /// Decrypts and reassembles the chunks of `encrypt_bytes`. Panics if a ciphertext does not
+    /// decrypt to a chunk.
+    pub fn decrypt_bytes(&self, hcs: &[HenselCode<T>]) -> Vec<u8> {
+        let mut data = Vec::new();
+        for hc in hcs {
+            let m = self.decrypt(hc.clone());
+            let bytes = m.to_bigint().map(|n| n.to_be_bytes());
+            match bytes.as_deref() {
+                Some([1, chunk @ ..]) => data.extend_from_slice(chunk),
+                _ => panic!("cannot decode '{}' as a chunk of bytes", m),
             }
         }
-        Self::new(
-            primes[0].clone(),
-            primes[1].clone(),
-            primes[2].clone(),
-            primes[3].clone(),
-            primes[4].clone(),
-        )
+        data
     }
-    /// returns a number `n` such that `n = n1 (mod p1)`, `n = n2 (mod p2)`, `n = n3 (mod p3)`
+
+    /// Returns how much the ciphertext `hc` can still grow before `decrypt` fails: the gap
+    /// between the size estimated by `noise` and `DecryptContext::bound`, or zero if the bound is
+    /// reached.
+    pub fn remaining_capacity(&self, hc: &HenselCode<T>) -> T {
+        self.decrypt_context()
+            .bound()
+            .saturating_sub(&self.noise(hc))
+    }
+
+    /// Estimates the noise of `hc` as the largest of the numerator and denominator of the
+    /// rational recovered modulo `p4`. As `encrypt` currently adds a null noise, this is the size
+    /// of the (possibly wrapped) message itself.
+    fn noise(&self, hc: &HenselCode<T>) -> T {
+        let r_p4 = Rational::<T>::from(&hc.change_modulus(&self._p4));
+        if r_p4.num > r_p4.denom {
+            r_p4.num
+        } else {
+            r_p4.denom
+        }
+    }
+
+    /// Returns the largest multiplicative depth at which `decrypt` still succeeds on messages
+    /// whose numerators and denominators are at most `message_bound`: a ciphertext of depth `d`
+    /// holds values up to `message_bound^(2^d)`, which must stay within the bound of
+    /// `DecryptContext`. It is zero too when fresh messages already exceed that bound. Panics if
+    /// `message_bound < 2`, whose powers never grow.
+    pub fn max_depth(&self, message_bound: &T) -> usize {
+        if *message_bound < T::from_u128(2) {
+            panic!(
+                "cannot bound the depth of messages bounded by '{}'",
+                message_bound
+            )
+        }
+        let context = self.decrypt_context();
+        let mut depth = 0;
+        let mut magnitude = message_bound.mul(message_bound);
+        while magnitude <= *context.bound() {
+            depth += 1;
+            magnitude = match magnitude.checked_mul(&magnitude) {
+                Some(square) => square,
+                None => break,
+            };
+        }
+        depth
+    }
+
+    /// Encrypts the integer `n`, the same as encrypting `n/1`. Like any message with a unit
+    /// denominator, it is reduced modulo `g` directly by `encrypt` instead of going through the
+    /// rational encoding.
+    pub fn encrypt_int(&self, n: &T) -> HenselCode<T> {
+        self.encrypt(Rational::from(n.clone()))
+    }
+
+    /// Summarizes the internal magnitudes of `hc`, to understand why it fails to decrypt
+    pub fn inspect(&self, hc: &HenselCode<T>) -> CiphertextStats<T> {
+        let noise = self.noise(hc);
+        CiphertextStats {
+            residues: self.primes().map(|p| hc.res.rem(p)).collect(),
+            decryptable: self.try_decrypt(hc).is_ok(),
+            noise,
+        }
+    }
+
+    /// Encrypts any plaintext type which can be encoded as a HenselCode. The encoding is additive,
+    /// so adding the encoding of `m` modulo `g` to a fresh encryption of zero gives the same
+    /// ciphertext as encrypting `m` as a rational.
+    pub fn encrypt_encoded<M: Encode<T>>(&self, m: &M) -> Result<HenselCode<T>, CodingError> {
+        let encoded = m.encode(self.prime_product())?;
+        Ok(self.encrypt(Rational::from(T::zero())) + encoded)
+    }
+
+    /// Decrypts `hc` and decodes the message as any plaintext type which can be decoded from its
+    /// HenselCode modulo `p1`. The sign of a negative message is carried over to `p1`.
+    pub fn decrypt_decoded<M: Decode<T>>(&self, hc: HenselCode<T>) -> Result<M, CodingError> {
+        let r_p4 = Rational::<T>::from(&hc.change_modulus(&self._p4));
+        // the reconstruction leaves the sign in the denominator, as a residue modulo p4
+        let (negative, denom) = new_hensel_code(&self._p4, &r_p4.denom).to_signed_bigint();
+        let encoded = encode(
+            &Rational {
+                num: r_p4.num,
+                denom,
+            },
+            &self._p1,
+        )?;
+        M::decode(&if negative { -encoded } else { encoded })
+    }
+
+    /// Returns the reconstruction bounds of `p1` and `p4` used by `try_decrypt`
+    pub fn decrypt_context(&self) -> DecryptContext<T> {
+        DecryptContext::new(&self._p1, &self._p4)
+    }
+
+    /// Same as `decrypt`, returning an error instead of a meaningless message when the
+    /// decrypted message exceeds the reconstruction bounds
+    pub fn try_decrypt(&self, hc: &HenselCode<T>) -> Result<Rational<T>, DecryptError> {
+        self.decrypt_context().try_decrypt(hc)
+    }
+
+    /// returns a number `n` such that `n = n1 (mod p1)`, `n = n2 (mod p2)`, `n = n3 (mod p3)`.
+    /// Panics rather than wrapping if the intermediate values do not fit in `T`, which needs
+    /// `p2^2` and `p3^2` to fit besides `p1*p2*p3`.
     fn chinese_remainder(&self, n1: T, n2: T, n3: T) -> HenselCode<T> {
         let hc1 = new_hensel_code(&self._p1, &n1);
         let hc2 = new_hensel_code(&self._p2, &n2);
         let hc3 = new_hensel_code(&self._p3, &n3);
-        chinese_remainder(chinese_remainder(hc1, hc2), hc3)
+        match checked_chinese_remainder(hc1, hc2)
+            .and_then(|hc12| checked_chinese_remainder(hc12, hc3))
+        {
+            Some(hc) => hc,
+            None => panic!(
+                "cannot combine residues modulo {}, {}, {} without overflowing",
+                self._p1, self._p2, self._p3
+            ),
+        }
     }
 }
 
-impl<T: BigIntTrait> EncryptionScheme<T> for PrivateKeySchemeCryptographicParameters<T> {
-    fn encrypt(&self, m: Rational<T>) -> HenselCode<T> {
+/// Multiplies the `primes`, returning `None` if the product does not fit in `T`. For
+/// crypto-bigint, `checked_mul` computes the widening product with `mul_wide` and checks that its
+/// high half is zero.
+fn checked_prime_product<T: BigIntTrait>(primes: &[&T]) -> Option<T> {
+    primes
+        .iter()
+        .try_fold(T::one(), |product, p| product.checked_mul(p))
+}
+
+/// Checks that `r` is within the Farey bound under which it is uniquely reconstructed from its
+/// HenselCode modulo `p`
+fn fits_reconstruction_bound<T: BigIntTrait>(r: &Rational<T>, p: &T) -> bool {
+    let n_max = T::farey_bound(p);
+    r.clamp_to_bound(&n_max, &n_max).is_some()
+}
+
+impl<T: BigIntTrait + Bounded> PrivateKeySchemeCryptographicParameters<T> {
+    /// Same as `generate_with_config`, first rejecting bit sizes whose product may not fit in
+    /// `T` without generating any prime
+    pub fn generate_bounded(cfg: &KeyGenConfig) -> Result<Self, ParamError> {
+        if !T::fits(cfg.prime_bits.iter().sum()) {
+            return Err(ParamError::ProductOverflow);
+        }
+        Self::generate_with_config(cfg)
+    }
+}
+
+/// Parses a private key from a string of five comma-separated decimal primes `"p1,p2,p3,p4,p5"`
+impl<T: BigIntTrait> TryFrom<&str> for PrivateKeySchemeCryptographicParameters<T> {
+    type Error = ParamError;
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        let primes = s
+            .split(',')
+            .map(|p| {
+                let p = p.trim();
+                p.parse::<T>()
+                    .map_err(|_| ParamError::InvalidNumber(p.to_string()))
+            })
+            .collect::<Result<Vec<T>, ParamError>>()?;
+        Self::from_primes(&primes)
+    }
+}
+
+impl<T: BigIntTrait> PrivateKeySchemeCryptographicParameters<T> {
+    /// Samples the noise of a fresh ciphertext
+    fn sample_noise(&self) -> NoiseReport<T> {
         let delta_max: T = self._p1.mul(&self._p2).mul(&self._p3).mul(&self._p5);
-        let g: T = delta_max.mul(&self._p4);
-        let s1 = T::random_mod(&self._p1);
-        let s2 = T::random_mod(&self._p2);
-        let s3 = T::random_mod(&self._p3);
-        let delta = T::random_mod(&delta_max);
+        NoiseReport {
+            s1: T::random_mod(&self._p1),
+            s2: T::random_mod(&self._p2),
+            s3: T::random_mod(&self._p3),
+            delta: T::random_mod(&delta_max),
+            delta_max,
+        }
+    }
 
-        let _dp4 = new_hensel_code(&g, &(delta.mul(&self._p4)));
+    /// Switches `hc` to a smaller modulus by dropping the prime of index `drop_prime_index`
+    /// (`0` for `p1`, ..., `4` for `p5`) from its modulus. Decryption only reads the residue
+    /// modulo `p4`, which reduction preserves, so the plaintext and the noise are unchanged, while
+    /// later operations run on smaller numbers. The ciphertext can then only be combined with
+    /// ciphertexts switched to the same modulus. Panics when asked to drop `p4`, or a prime which
+    /// does not divide the modulus of `hc`.
+    pub fn mod_switch(&self, hc: HenselCode<T>, drop_prime_index: usize) -> HenselCode<T> {
+        let p = match self.primes().nth(drop_prime_index) {
+            Some(p) if drop_prime_index != 3 => p,
+            _ => panic!(
+                "cannot drop the prime of index {} from '{}'",
+                drop_prime_index, hc
+            ),
+        };
+        if !hc.modulus.rem(p).is_zero() {
+            panic!("cannot drop '{}' from the modulus of '{}'", p, hc);
+        }
+        hc.change_modulus(&hc.modulus.div(p))
+    }
 
-        let hc_noise = self.chinese_remainder(T::zero(), s2.clone(), s3.clone());
+    /// Returns the encryption of zero `delta*p4` modulo `g`, which vanishes modulo `p4` so that
+    /// adding it to a ciphertext does not change its decryption
+    fn zero_encoding(&self, delta: &T) -> HenselCode<T> {
+        new_hensel_code(&self.g, &delta.mul(&self._p4))
+    }
+
+    /// Rerandomizes the ciphertext `hc` by adding a fresh encryption of zero, so that it cannot
+    /// be linked to other encryptions of the same message. The decryption is unchanged.
+    pub fn rerandomize(&self, hc: HenselCode<T>) -> HenselCode<T> {
+        let delta_max: T = self._p1.mul(&self._p2).mul(&self._p3).mul(&self._p5);
+        hc + self.zero_encoding(&T::random_mod(&delta_max))
+    }
+
+    /// Encrypts `m` with the sampled `noise`
+    fn encrypt_with_noise(&self, m: Rational<T>, noise: &NoiseReport<T>) -> HenselCode<T> {
+        let g = self.prime_product();
+        let _dp4 = self.zero_encoding(&noise.delta);
+
+        let p123 = self._p1.mul(&self._p2).mul(&self._p3);
+        let hc_noise = self.chinese_remainder(T::zero(), noise.s2.clone(), noise.s3.clone());
+        debug_assert!(
+            hc_noise.modulus == p123 && hc_noise.res < p123,
+            "noise '{}' is not reduced modulo p1*p2*p3 = {}",
+            hc_noise,
+            p123
+        );
+        #[cfg(feature = "tracing")]
+        tracing::trace!(%hc_noise);
         assert_eq!(hc_noise.res.rem(&self._p1), T::zero());
-        assert_eq!(hc_noise.res.rem(&self._p2), s2);
-        assert_eq!(hc_noise.res.rem(&self._p3), s3);
+        assert_eq!(hc_noise.res.rem(&self._p2), noise.s2);
+        assert_eq!(hc_noise.res.rem(&self._p3), noise.s3);
 
         // convert to a Rational
         let r_noise = Rational::from(&hc_noise);
-        let p123 = self._p1.mul(&self._p2).mul(&self._p3);
+        debug_assert!(
+            !r_noise.denom.is_zero(),
+            "noise '{}' reconstructs to a zero denominator",
+            hc_noise
+        );
+        #[cfg(feature = "tracing")]
+        tracing::trace!(%r_noise);
         assert_eq!(
             r_noise.num.rem(&p123),
             hc_noise.res.mul(&r_noise.denom).rem(&p123)
         );
 
         // create a Rational from s1
-        let _rs1 = Rational {
-            num: s1,
-            denom: T::one(),
-        };
+        let _rs1 = Rational::from(noise.s1.clone());
         // multiply rational encoding of zero by s1
         // let mut rational_term: Rational<T> = rs1 * r_noise;
         // DEBUG: enforce null noise
-        let mut rational_term: Rational<T> = Rational {
-            num: T::zero(),
-            denom: T::one(),
-        };
+        let mut rational_term: Rational<T> = Rational::from(T::zero());
 
         // add the message `m` (a Rational by assumption)
-        rational_term = rational_term + m;
-        rational_term = rational_term.reduce();
-        println!("rational term: {rational_term}");
+        rational_term = match rational_term.checked_add(&m) {
+            Some(sum) => sum,
+            None => panic!(
+                "cannot add '{}' to '{}' without overflowing",
+                m, rational_term
+            ),
+        };
+        #[cfg(feature = "tracing")]
+        tracing::trace!(%rational_term);
+        debug_assert!(
+            fits_reconstruction_bound(&rational_term, &self._p4)
+                && fits_reconstruction_bound(&rational_term, &self._p1),
+            "rational term '{}' exceeds the reconstruction bounds of p1 = {} and p4 = {}",
+            rational_term,
+            self._p1,
+            self._p4
+        );
 
         // convert to HenselCode, add another noise `delta*p4`
         // return the result
         // HenselCode::from((&g, &rational_term)) + dp4
-        HenselCode::from((&g, &rational_term))
+        // an integer term is its own residue, which skips the inversion of the denominator
+        let hc = if rational_term.denom == T::one() {
+            new_hensel_code(g, &rational_term.num)
+        } else {
+            HenselCode::from((g, &rational_term))
+        };
+        #[cfg(feature = "tracing")]
+        tracing::trace!(%hc);
+        hc
     }
 
+    /// Same as `encrypt`, also returning the noise which was sampled. This leaks secrets, and is
+    /// only meant for debugging. As the noise is null, the report only shows what was sampled: it
+    /// is not a measure of the noise in the returned ciphertext.
+    #[cfg(feature = "debug-internals")]
+    pub fn encrypt_with_report(&self, m: Rational<T>) -> (HenselCode<T>, NoiseReport<T>) {
+        let noise = self.sample_noise();
+        (self.encrypt_with_noise(m, &noise), noise)
+    }
+}
+
+impl<T: BigIntTrait> EncryptionScheme<T> for PrivateKeySchemeCryptographicParameters<T> {
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all))]
+    fn encrypt(&self, m: Rational<T>) -> HenselCode<T> {
+        self.encrypt_with_noise(m, &self.sample_noise())
+    }
+
+    /// Decrypts `hc`. The conversions between rationals and HenselCodes have no early return or
+    /// branch selected by the values being decrypted, so that the decryption of special values
+    /// (e.g. zero) is not told apart by its timing. The euclidean algorithms still run a number
+    /// of steps which depends on their input, and the big int arithmetic may itself be variable
+    /// time depending on the backend.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all))]
     fn decrypt(&self, hc: HenselCode<T>) -> Rational<T> {
-        let hc_p4 = new_hensel_code(&self._p4, &hc.res);
-        println!("hensel code mod p4: {hc_p4}");
+        let hc_p4 = hc.change_modulus(&self._p4);
         let r_p4: Rational<T> = Rational::<T>::from(&hc_p4);
-        println!(
-            "hensel code decrypted: {}",
-            HenselCode::<T>::from((&self._p1, &r_p4))
-        );
-        Rational::<T>::from(&HenselCode::<T>::from((&self._p1, &r_p4)))
+        #[cfg(feature = "tracing")]
+        tracing::trace!(%hc_p4, %r_p4);
+        let m = Rational::<T>::from(&HenselCode::<T>::from((&self._p1, &r_p4)));
+        #[cfg(feature = "tracing")]
+        tracing::trace!(%m);
+        m
     }
 }
 
@@ -136,28 +809,75 @@ pub struct PublicKeySchemeCryptographicParameters<T: BigIntTrait> {
     _p2: T,
     _p3: T,
     _p4: T,
-    lambda: u32,
-    g: T,
-    g_prime: T,
-    e: HenselCode<T>,
+    public_key: PublicKey<T>,
+}
+
+/// The public part of `PublicKeySchemeCryptographicParameters`, which suffices to encrypt: the
+/// security parameter `lambda`, the moduli `g = p1*p2*p3*p4` and `g_prime = p3*p4`, and the
+/// encryption of zero `e`. It holds none of the primes.
+#[derive(Clone, Debug)]
+pub struct PublicKey<T: BigIntTrait> {
+    pub lambda: u32,
+    pub g: T,
+    pub g_prime: T,
+    pub e: HenselCode<T>,
+}
+
+impl<T: BigIntTrait> PublicKey<T> {
+    /// Encrypts `m` knowing only the public values
+    pub fn public_encrypt(&self, m: Rational<T>) -> HenselCode<T> {
+        let s1: T = T::random_mod(&T::from_u128(2).pow((self.lambda - 1) as u128));
+        let s2: T = T::random_mod(&T::from_u128(2).pow((self.lambda - 1) as u128));
+        let hc_prime_res = (new_hensel_code(&self.g_prime, &s1.mul(&self.e.res))
+            + HenselCode::from((&self.g_prime, &m)))
+        .res;
+        // the term `delta*g^2` of the scheme vanishes modulo `g`, so the secret range of `delta`
+        // is not needed
+        let encrypted_res = hc_prime_res.add(&s2.mul(&self.g_prime));
+        new_hensel_code(&self.g, &encrypted_res)
+    }
 }
 
 impl<T: BigIntTrait> PublicKeySchemeCryptographicParameters<T> {
+    /// Panics if the public modulus `g = p1*p2*p3*p4` does not fit in `T`, as it would silently
+    /// wrap for fixed-size big ints
     pub fn new(_p1: T, _p2: T, _p3: T, _p4: T, lambda: u32, e: HenselCode<T>) -> Self {
-        let g = _p1.mul(&_p2.mul(&_p3.mul(&_p4)));
         let g_prime = _p3.mul(&_p4);
+        let g = match _p3
+            .checked_mul(&_p4)
+            .and_then(|p34| _p2.checked_mul(&p34))
+            .and_then(|p234| _p1.checked_mul(&p234))
+        {
+            Some(g) => g,
+            None => panic!(
+                "the product of the primes {}, {}, {}, {} does not fit in the big int type",
+                _p1, _p2, _p3, _p4
+            ),
+        };
         Self {
             _p1,
             _p2,
             _p3,
             _p4,
-            lambda,
-            g,
-            g_prime,
-            e,
+            public_key: PublicKey {
+                lambda,
+                g,
+                g_prime,
+                e,
+            },
         }
     }
 
+    /// Returns the public values, which may be shared to let others encrypt
+    pub fn public_key(&self) -> &PublicKey<T> {
+        &self.public_key
+    }
+
+    /// Same as `encrypt`, only reading the public key
+    pub fn public_encrypt(&self, m: Rational<T>) -> HenselCode<T> {
+        self.public_key.public_encrypt(m)
+    }
+
     /// generates keys (in particular 5 distinct primes) from the security parameter `lambda` and the number `number_operations` of homomorphic operations allowed on ciphertexts
     pub fn new_from_number_operations(lambda: u32, number_operations: u32) -> Self {
         Self::new_from_params(lambda, Self::d_from_number_operations(number_operations))
@@ -211,21 +931,11 @@ impl<T: BigIntTrait> PublicKeySchemeCryptographicParameters<T> {
 
 impl<T: BigIntTrait> EncryptionScheme<T> for PublicKeySchemeCryptographicParameters<T> {
     fn encrypt(&self, m: Rational<T>) -> HenselCode<T> {
-        let s1: T = T::random_mod(&T::from_u128(2).pow((self.lambda - 1) as u128));
-        let s2: T = T::random_mod(&T::from_u128(2).pow((self.lambda - 1) as u128));
-        let p12 = self._p1.mul(&self._p2);
-        let delta: T = T::random_mod(&p12.mul(&self._p4).sub(&p12)).add(&p12);
-        let hc_prime_res = (new_hensel_code(&self.g_prime, &s1.mul(&self.e.res))
-            + HenselCode::from((&self.g_prime, &m)))
-        .res;
-        let encrypted_res = hc_prime_res
-            .add(&s2.mul(&self.g_prime))
-            .add(&delta.mul(&self.g.pow(2)));
-        new_hensel_code(&self.g, &encrypted_res)
+        self.public_encrypt(m)
     }
 
     fn decrypt(&self, hc: HenselCode<T>) -> Rational<T> {
-        let hc_p3 = new_hensel_code(&self._p3, &hc.res);
+        let hc_p3 = hc.change_modulus(&self._p3);
         let r_p3: Rational<T> = Rational::<T>::from(&hc_p3);
         Rational::<T>::from(&HenselCode::<T>::from((&self._p1, &r_p3)))
     }
@@ -233,7 +943,10 @@ impl<T: BigIntTrait> EncryptionScheme<T> for PublicKeySchemeCryptographicParamet
 
 #[cfg(test)]
 mod tests {
-    use super::PrivateKeySchemeCryptographicParameters;
+    use super::{
+        EncryptError, ParamError, PrivateKeySchemeCryptographicParameters,
+        PublicKeySchemeCryptographicParameters,
+    };
     use crate::bigint::BigIntTrait;
     use crate::hensel_code;
 
@@ -249,13 +962,13 @@ mod tests {
             T::from_u128(13),
             T::from_u128(17),
         );
-        let crypto_param = PrivateKeySchemeCryptographicParameters {
-            _p1: p1.clone(),
-            _p2: p2.clone(),
-            _p3: p3.clone(),
-            _p4: p4.clone(),
-            _p5: p5.clone(),
-        };
+        let crypto_param = PrivateKeySchemeCryptographicParameters::new(
+            p1.clone(),
+            p2.clone(),
+            p3.clone(),
+            p4.clone(),
+            p5.clone(),
+        );
         let (n1, n2, n3) = (T::from_u128(38), T::from_u128(2), T::one());
         let result = crypto_param.chinese_remainder(n1.clone(), n2.clone(), n3.clone());
 
@@ -271,4 +984,829 @@ mod tests {
         assert_eq!(result.res, hc.res);
         println!("{} : {}", hc, result);
     }
+
+    #[test]
+    fn chinese_remainder_small_limbs() {
+        type T2 = crate::bigint::WrappingCryptoBigInt<2>;
+        let p = |n| T2::from_u128(n);
+        // the five primes need 127 bits, while the products of the residues by the inverses in
+        // `hensel_code::chinese_remainder` need up to 155 bits and would wrap
+        let crypto_param = PrivateKeySchemeCryptographicParameters::new(
+            p(2147483647),
+            p(2147483629),
+            p(2147483587),
+            p(2147483579),
+            p(7),
+        );
+        let (n1, n2, n3) = (p(2147483640), p(2147483620), p(2147483580));
+        let result = crypto_param.chinese_remainder(n1.clone(), n2.clone(), n3.clone());
+        assert_eq!(
+            result.modulus,
+            p(2147483647).mul(&p(2147483629)).mul(&p(2147483587))
+        );
+        assert_eq!(result.res.rem(&p(2147483647)), n1);
+        assert_eq!(result.res.rem(&p(2147483629)), n2);
+        assert_eq!(result.res.rem(&p(2147483587)), n3);
+    }
+
+    #[test]
+    fn private_key_from_str() {
+        let crypto_param =
+            PrivateKeySchemeCryptographicParameters::<T>::try_from("4919, 7,11,13,17").unwrap();
+        assert_eq!(crypto_param._p1, T::from_u128(4919));
+        assert_eq!(crypto_param._p2, T::from_u128(7));
+        assert_eq!(crypto_param._p5, T::from_u128(17));
+
+        assert_eq!(
+            PrivateKeySchemeCryptographicParameters::<T>::try_from("4919,7,11,13").err(),
+            Some(ParamError::WrongNumberOfPrimes {
+                expected: 5,
+                found: 4
+            })
+        );
+        assert_eq!(
+            PrivateKeySchemeCryptographicParameters::<T>::try_from("4919,7,eleven,13,17").err(),
+            Some(ParamError::InvalidNumber("eleven".to_string()))
+        );
+    }
+
+    #[test]
+    fn errors_box_as_dyn_error() {
+        use std::error::Error;
+
+        fn parse(s: &str) -> Result<PrivateKeySchemeCryptographicParameters<T>, Box<dyn Error>> {
+            Ok(PrivateKeySchemeCryptographicParameters::try_from(s)?)
+        }
+        assert!(parse("7919,37,41,5897,7759").is_ok());
+        let err = parse("7919,37,forty-one,5897,7759").err().unwrap();
+        assert_eq!(err.to_string(), "cannot parse 'forty-one' as a number");
+        assert!(err.source().is_none());
+        let err: Box<dyn Error> = Box::new(EncryptError::MessageOutOfBounds);
+        assert_eq!(
+            err.to_string(),
+            "message does not fit in the plaintext modulus"
+        );
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "exceeds the reconstruction bounds")]
+    fn encrypt_out_of_range_message() {
+        use super::EncryptionScheme;
+        use crate::rational::Rational;
+
+        let crypto_param =
+            PrivateKeySchemeCryptographicParameters::<T>::try_from("7919,37,41,5897,7759").unwrap();
+        // floor(sqrt((5897 - 1)/2)) = 54 < 1000
+        let message = Rational::<T> {
+            num: T::from_u128(1000),
+            denom: T::from_u128(3),
+        };
+        crypto_param.encrypt(message);
+    }
+
+    #[test]
+    fn encrypt_with_prime_subset() {
+        use crate::hensel_code::HenselCode;
+        use crate::rational::Rational;
+
+        let crypto_param =
+            PrivateKeySchemeCryptographicParameters::<T>::try_from("7919,37,41,5897,7759").unwrap();
+        let message = Rational::<T> {
+            num: T::from_u128(10),
+            denom: T::from_u128(3),
+        };
+        let hc = crypto_param
+            .encrypt_with_primes(message.clone(), &[0, 1, 2])
+            .unwrap();
+        assert_eq!(hc.modulus, T::from_u128(7919 * 37 * 41));
+        let decrypted = crypto_param.decrypt_with_primes(&hc, &[0, 1, 2]).unwrap();
+        assert_eq!(decrypted, message);
+
+        for indices in [&[0, 1, 2][..], &[3, 0], &[4], &[4, 3, 1]] {
+            let hc = crypto_param
+                .encrypt_with_primes(message.clone(), indices)
+                .unwrap();
+            // the noise vanishes modulo the decryption prime
+            let p = crypto_param.primes().nth(indices[0]).unwrap();
+            assert_eq!(
+                hc.change_modulus(p).res,
+                HenselCode::from((p, &message)).res
+            );
+            let decrypted = crypto_param.decrypt_with_primes(&hc, indices).unwrap();
+            assert_eq!(decrypted, message);
+        }
+
+        for indices in [&[][..], &[0, 5], &[1, 1]] {
+            assert_eq!(
+                crypto_param
+                    .encrypt_with_primes(message.clone(), indices)
+                    .err(),
+                Some(ParamError::InvalidPrimeIndices(indices.to_vec()))
+            );
+        }
+    }
+
+    #[test]
+    fn encrypt_bytes_round_trip() {
+        let p = |n| T::from_u128(n);
+        // p1 = 2^61 - 1 and p4 = 2^89 - 1, the Farey bound of p1 is 2^30 - 1
+        let crypto_param = PrivateKeySchemeCryptographicParameters::<T>::new(
+            p((1 << 61) - 1),
+            p(37),
+            p(41),
+            p((1 << 89) - 1),
+            p(7759),
+        );
+        assert_eq!(crypto_param.bytes_per_chunk(), 2);
+
+        let data = b"\0p-adic\0\0fhe\0";
+        let ciphertexts = crypto_param.encrypt_bytes(data);
+        assert_eq!(ciphertexts.len(), 7);
+        assert_eq!(crypto_param.decrypt_bytes(&ciphertexts), data);
+        assert!(crypto_param.encrypt_bytes(&[]).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot encrypt bytes")]
+    fn encrypt_bytes_with_small_primes() {
+        let crypto_param =
+            PrivateKeySchemeCryptographicParameters::<T>::try_from("7919,37,41,5897,7759").unwrap();
+        crypto_param.encrypt_bytes(b"fhe");
+    }
+
+    #[test]
+    fn verify() {
+        use super::EncryptionScheme;
+        use crate::rational::Rational;
+
+        let crypto_param =
+            PrivateKeySchemeCryptographicParameters::<T>::try_from("7919,37,41,5897,7759").unwrap();
+        let r = |num, denom| Rational::<T> {
+            num: T::from_u128(num),
+            denom: T::from_u128(denom),
+        };
+        let sum = crypto_param.encrypt(r(1, 2)) + crypto_param.encrypt(r(1, 3));
+        assert!(crypto_param.verify(&sum, &r(5, 6)));
+        assert!(crypto_param.verify(&sum, &r(10, 12)));
+        assert!(!crypto_param.verify(&sum, &r(2, 5)));
+    }
+
+    #[test]
+    fn decrypt_to_f64() {
+        use super::EncryptionScheme;
+        use crate::rational::Rational;
+
+        let crypto_param =
+            PrivateKeySchemeCryptographicParameters::<T>::try_from("7919,37,41,5897,7759").unwrap();
+        let message = Rational::<T> {
+            num: T::from_u128(3),
+            denom: T::from_u128(4),
+        };
+        assert_eq!(
+            crypto_param.decrypt_to_f64(crypto_param.encrypt(message)),
+            0.75
+        );
+    }
+
+    #[test]
+    fn remaining_capacity_shrinks() {
+        use super::EncryptionScheme;
+        use crate::rational::Rational;
+
+        let crypto_param =
+            PrivateKeySchemeCryptographicParameters::<T>::try_from("7919,37,41,5897,7759").unwrap();
+        let ciphertext = crypto_param.encrypt(Rational::<T> {
+            num: T::from_u128(2),
+            denom: T::from_u128(3),
+        });
+        // values must stay within floor(sqrt((5897 - 1)/2)) = 54, the smaller bound
+        let capacity1 = crypto_param.remaining_capacity(&ciphertext);
+        assert_eq!(capacity1, T::from_u128(54 - 3));
+        let squared = &ciphertext * &ciphertext;
+        let capacity2 = crypto_param.remaining_capacity(&squared);
+        assert_eq!(capacity2, T::from_u128(54 - 9));
+        let cubed = &squared * &ciphertext;
+        let capacity3 = crypto_param.remaining_capacity(&cubed);
+        assert_eq!(capacity3, T::from_u128(54 - 27));
+        assert_eq!(
+            crypto_param.remaining_capacity(&(&cubed * &cubed)),
+            T::zero()
+        );
+        // 54/1 reaches the bound of p4 but still decrypts, while 55/1 exceeds it
+        let at_bound = crypto_param.encrypt(Rational::from(T::from_u128(54)));
+        assert_eq!(crypto_param.remaining_capacity(&at_bound), T::zero());
+        assert!(crypto_param.try_decrypt(&at_bound).is_ok());
+        let over_bound = at_bound.add_scalar(&T::one());
+        assert_eq!(crypto_param.remaining_capacity(&over_bound), T::zero());
+        assert!(crypto_param.try_decrypt(&over_bound).is_err());
+    }
+
+    #[test]
+    fn encrypt_int() {
+        use super::EncryptionScheme;
+        use crate::rational::Rational;
+
+        let crypto_param =
+            PrivateKeySchemeCryptographicParameters::<T>::try_from("7919,37,41,5897,7759").unwrap();
+        let g = crypto_param.prime_product();
+        for n in [0, 1, 5, 54] {
+            let n = T::from_u128(n);
+            let hc = crypto_param.encrypt_int(&n);
+            // same ciphertext as the general encoding, as the noise is null
+            let general = hensel_code::HenselCode::from((g, &Rational::from(n.clone())));
+            assert_eq!(hc.res, general.res);
+            assert_eq!(crypto_param.decrypt(hc), Rational::from(n.clone()));
+            assert_eq!(
+                crypto_param.decrypt(crypto_param.encrypt(Rational::from(n.clone()))),
+                Rational::from(n)
+            );
+        }
+    }
+
+    #[test]
+    fn max_depth() {
+        use super::EncryptionScheme;
+        use crate::ciphertext::Ciphertext;
+        use crate::rational::Rational;
+
+        let crypto_param =
+            PrivateKeySchemeCryptographicParameters::<T>::try_from("7919,37,41,5897,7759").unwrap();
+        // the bound is floor(sqrt((5897 - 1)/2)) = 54
+        assert_eq!(crypto_param.max_depth(&T::from_u128(3)), 1);
+        assert_eq!(crypto_param.max_depth(&T::from_u128(2)), 2);
+        assert_eq!(crypto_param.max_depth(&T::from_u128(8)), 0);
+
+        let message = Rational::<T> {
+            num: T::from_u128(2),
+            denom: T::from_u128(3),
+        };
+        let depth_0 = Ciphertext::new(crypto_param.encrypt(message.clone()));
+        let depth_1 = depth_0.mul_ciphertexts(&depth_0);
+        assert_eq!(depth_1.mul_depth, 1);
+        assert_eq!(
+            crypto_param.try_decrypt(&depth_1.hc),
+            Ok(&message * &message)
+        );
+        // 16/81 exceeds the bound at depth 2
+        let depth_2 = depth_1.mul_ciphertexts(&depth_1);
+        assert!(depth_2.mul_depth > crypto_param.max_depth(&T::from_u128(3)));
+        assert!(crypto_param.try_decrypt(&depth_2.hc).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot bound the depth")]
+    fn max_depth_of_units() {
+        let crypto_param =
+            PrivateKeySchemeCryptographicParameters::<T>::try_from("7919,37,41,5897,7759").unwrap();
+        crypto_param.max_depth(&T::one());
+    }
+
+    #[test]
+    fn rerandomize() {
+        use super::EncryptionScheme;
+        use crate::rational::Rational;
+
+        let crypto_param =
+            PrivateKeySchemeCryptographicParameters::<T>::try_from("7919,37,41,5897,7759").unwrap();
+        let message = Rational::<T> {
+            num: T::from_u128(2),
+            denom: T::from_u128(3),
+        };
+        let ciphertext = crypto_param.encrypt(message.clone());
+        let rerandomized = crypto_param.rerandomize(ciphertext.clone());
+        assert_eq!(rerandomized.modulus, ciphertext.modulus);
+        assert_ne!(rerandomized.res, ciphertext.res);
+        assert!(crypto_param.verify(&rerandomized, &message));
+        let squared = &rerandomized * &crypto_param.rerandomize(ciphertext);
+        assert!(crypto_param.verify(&squared, &(&message * &message)));
+    }
+
+    #[test]
+    fn mod_switch() {
+        use super::EncryptionScheme;
+        use crate::rational::Rational;
+
+        let crypto_param =
+            PrivateKeySchemeCryptographicParameters::<T>::try_from("7919,37,41,5897,7759").unwrap();
+        let message = Rational::<T> {
+            num: T::from_u128(2),
+            denom: T::from_u128(3),
+        };
+        let ciphertext = crypto_param.rerandomize(crypto_param.encrypt(message.clone()));
+        let switched = crypto_param.mod_switch(ciphertext.clone(), 4);
+        assert_eq!(switched.modulus, T::from_u128(7919 * 37 * 41 * 5897));
+        assert!(crypto_param.verify(&switched, &message));
+        let switched = crypto_param.mod_switch(switched, 1);
+        assert_eq!(switched.modulus, T::from_u128(7919 * 41 * 5897));
+        assert!(crypto_param.verify(&switched, &message));
+        assert!(crypto_param.verify(&(&switched * &switched), &(&message * &message)));
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot drop the prime of index 3")]
+    fn mod_switch_p4() {
+        use super::EncryptionScheme;
+
+        let crypto_param =
+            PrivateKeySchemeCryptographicParameters::<T>::try_from("7919,37,41,5897,7759").unwrap();
+        let ciphertext = crypto_param.encrypt(T::one().into());
+        crypto_param.mod_switch(ciphertext, 3);
+    }
+
+    #[test]
+    fn try_decrypt() {
+        use super::{DecryptError, EncryptionScheme};
+        use crate::rational::Rational;
+
+        let crypto_param =
+            PrivateKeySchemeCryptographicParameters::<T>::try_from("7919,37,41,5897,7759").unwrap();
+        let context = crypto_param.decrypt_context();
+        assert_eq!(context.bound_p1, T::from_u128(62));
+        assert_eq!(context.bound_p4, T::from_u128(54));
+        assert_eq!(*context.bound(), T::from_u128(54));
+
+        let message = Rational::<T> {
+            num: T::from_u128(47),
+            denom: T::from_u128(52),
+        };
+        let ciphertext = crypto_param.encrypt(message.clone());
+        assert_eq!(crypto_param.try_decrypt(&ciphertext), Ok(message));
+        // 2209/2704 exceeds the bound of p4, and reconstructs to 52/77 modulo p4
+        let squared = &ciphertext * &ciphertext;
+        assert_eq!(
+            crypto_param.try_decrypt(&squared),
+            Err(DecryptError::OutOfBounds)
+        );
+        // the bound floor(sqrt(5896/2)) = 54 itself is recovered
+        let at_bound = crypto_param.encrypt(Rational::from(T::from_u128(54)));
+        assert_eq!(
+            crypto_param.try_decrypt(&at_bound),
+            Ok(Rational::from(T::from_u128(54)))
+        );
+        assert_eq!(
+            crypto_param.try_decrypt(&at_bound.add_scalar(&T::one())),
+            Err(DecryptError::OutOfBounds)
+        );
+    }
+
+    #[test]
+    fn encrypt_generic_messages() {
+        use super::EncryptionScheme;
+        use crate::hensel_code::{Decode, Encode};
+        use crate::rational::Rational;
+
+        fn round_trip<M: Encode<T> + Decode<T>>(
+            crypto_param: &PrivateKeySchemeCryptographicParameters<T>,
+            m: &M,
+        ) -> M {
+            let ciphertext = crypto_param.encrypt_encoded(m).unwrap();
+            crypto_param.decrypt_decoded(ciphertext).unwrap()
+        }
+
+        let crypto_param =
+            PrivateKeySchemeCryptographicParameters::<T>::try_from("7919,37,41,5897,7759").unwrap();
+        assert_eq!(round_trip(&crypto_param, &42_i64), 42);
+        assert_eq!(round_trip(&crypto_param, &-42_i64), -42);
+        assert_eq!(round_trip(&crypto_param, &-1_i64), -1);
+        assert_eq!(
+            round_trip(&crypto_param, &T::from_u128(54)),
+            T::from_u128(54)
+        );
+        let r = Rational::<T> {
+            num: T::from_u128(2),
+            denom: T::from_u128(3),
+        };
+        assert_eq!(round_trip(&crypto_param, &r), r);
+        // the generic path gives the same ciphertext as `encrypt`
+        assert_eq!(
+            crypto_param.encrypt_encoded(&r).unwrap().res,
+            crypto_param.encrypt(r).res
+        );
+    }
+
+    #[test]
+    fn evaluate_circuit() {
+        use super::EncryptionScheme;
+        use crate::circuit::Circuit;
+        use crate::rational::Rational;
+
+        let crypto_param =
+            PrivateKeySchemeCryptographicParameters::<T>::try_from("7919,37,41,5897,7759").unwrap();
+        let rational = |num, denom| Rational::<T> {
+            num: T::from_u128(num),
+            denom: T::from_u128(denom),
+        };
+        let inputs = crypto_param
+            .encrypt_vec(&[rational(1, 2), rational(1, 3), rational(3, 5)])
+            .0;
+        // (x + y)*z = 5/6 * 3/5 = 1/2
+        let (x, y, z) = (Circuit::input(0), Circuit::input(1), Circuit::input(2));
+        let circuit = (x + y) * z;
+        let result = crypto_param.evaluate(&circuit, &inputs);
+        assert!(crypto_param.verify(&result, &rational(1, 2)));
+        let doubled = Circuit::scalar_mul(circuit, T::from_u128(2));
+        assert!(crypto_param.verify(&crypto_param.evaluate(&doubled, &inputs), &rational(1, 1)));
+    }
+
+    #[test]
+    fn inspect_ciphertexts() {
+        use super::EncryptionScheme;
+        use crate::rational::Rational;
+
+        let crypto_param =
+            PrivateKeySchemeCryptographicParameters::<T>::try_from("7919,37,41,5897,7759").unwrap();
+        let fresh = crypto_param.encrypt(Rational::<T> {
+            num: T::from_u128(2),
+            denom: T::from_u128(3),
+        });
+        let stats = crypto_param.inspect(&fresh);
+        assert_eq!(stats.noise, T::from_u128(3));
+        assert!(stats.decryptable);
+        let expected_residues: Vec<T> = crypto_param.primes().map(|p| fresh.res.rem(p)).collect();
+        assert_eq!(stats.residues, expected_residues);
+        // 2/3 = 5280 (mod 7919)
+        assert_eq!(stats.residues[0], T::from_u128(5280));
+
+        // 54 is within the Farey bounds of both p1 and p4
+        let at_bound = crypto_param.encrypt(Rational::from(T::from_u128(54)));
+        let stats = crypto_param.inspect(&at_bound);
+        assert_eq!(stats.noise, T::from_u128(54));
+        assert!(stats.decryptable);
+        // 55 is within the Farey bound of p1, but not of p4
+        let stats = crypto_param.inspect(&at_bound.add_scalar(&T::one()));
+        assert!(!stats.decryptable);
+
+        let mut over_noised = fresh.clone();
+        for _ in 0..5 {
+            over_noised = &over_noised * &fresh;
+        }
+        let stats = crypto_param.inspect(&over_noised);
+        assert!(!stats.decryptable);
+        assert!(stats.noise >= T::from_u128(62));
+    }
+
+    #[test]
+    fn encrypt_bounded() {
+        use super::EncryptionScheme;
+        use crate::rational::Rational;
+
+        let crypto_param =
+            PrivateKeySchemeCryptographicParameters::<T>::try_from("7919,37,41,5897,7759").unwrap();
+        // floor(sqrt(101/2)) = 7
+        let plaintext_modulus = T::from_u128(101);
+        let m1 = Rational::<T> {
+            num: T::from_u128(5),
+            denom: T::from_u128(7),
+        };
+        let ciphertext = crypto_param
+            .encrypt_bounded(m1.clone(), &plaintext_modulus)
+            .unwrap();
+        assert_eq!(crypto_param.decrypt(ciphertext), m1);
+
+        let m2 = Rational::<T> {
+            num: T::from_u128(5),
+            denom: T::from_u128(8),
+        };
+        assert_eq!(
+            crypto_param.encrypt_bounded(m2, &plaintext_modulus).err(),
+            Some(EncryptError::MessageOutOfBounds)
+        );
+        assert_eq!(
+            crypto_param.encrypt_bounded(m1, &T::from_u128(7920)).err(),
+            Some(EncryptError::PlaintextModulusTooLarge)
+        );
+    }
+
+    #[test]
+    fn try_new_rejects_obvious_non_primes() {
+        let (p1, p2, p3, p4) = (
+            T::from_u128(4919),
+            T::from_u128(7),
+            T::from_u128(11),
+            T::from_u128(13),
+        );
+        assert!(PrivateKeySchemeCryptographicParameters::try_new(
+            p1.clone(),
+            p2.clone(),
+            p3.clone(),
+            p4.clone(),
+            T::from_u128(2)
+        )
+        .is_ok());
+        assert_eq!(
+            PrivateKeySchemeCryptographicParameters::try_new(
+                p1.clone(),
+                p2.clone(),
+                p3.clone(),
+                p4.clone(),
+                T::from_u128(4)
+            )
+            .err(),
+            Some(ParamError::InvalidPrime(T::from_u128(4).to_string()))
+        );
+        assert_eq!(
+            PrivateKeySchemeCryptographicParameters::try_new(p1, p2, p3, p4, T::zero()).err(),
+            Some(ParamError::InvalidPrime(T::zero().to_string()))
+        );
+        assert_eq!(
+            PrivateKeySchemeCryptographicParameters::<T>::try_from("4919,7,11,13,1").err(),
+            Some(ParamError::InvalidPrime(T::one().to_string()))
+        );
+    }
+
+    #[test]
+    fn try_new_rejects_non_coprime_primes() {
+        assert_eq!(
+            PrivateKeySchemeCryptographicParameters::<T>::try_from("7919,37,37,5897,7759").err(),
+            Some(ParamError::NotPairwiseCoprime)
+        );
+        assert_eq!(
+            PrivateKeySchemeCryptographicParameters::<T>::try_from("7919,15,37,5897,35").err(),
+            Some(ParamError::NotPairwiseCoprime)
+        );
+        assert!(
+            PrivateKeySchemeCryptographicParameters::<T>::try_from("7919,37,41,5897,7759").is_ok()
+        );
+    }
+
+    #[test]
+    fn from_primes_slice() {
+        let primes: Vec<T> = [4919, 7, 11, 13, 17]
+            .into_iter()
+            .map(T::from_u128)
+            .collect();
+        let crypto_param = PrivateKeySchemeCryptographicParameters::from_primes(&primes).unwrap();
+        assert_eq!(crypto_param.primes().cloned().collect::<Vec<T>>(), primes);
+        assert_eq!(
+            PrivateKeySchemeCryptographicParameters::from_primes(&primes[..3]).err(),
+            Some(ParamError::WrongNumberOfPrimes {
+                expected: 5,
+                found: 3
+            })
+        );
+    }
+
+    #[test]
+    fn decrypt_zero_and_non_zero() {
+        use super::EncryptionScheme;
+        use crate::rational::Rational;
+
+        // a zero residue used to take an early return in the reconstruction, it now goes through
+        // the same path as any other value
+        let crypto_param =
+            PrivateKeySchemeCryptographicParameters::<T>::try_from("7919,37,41,5897,7759").unwrap();
+        for (num, denom) in [(0, 1), (1, 1), (2, 3), (20, 43)] {
+            let message = Rational::<T> {
+                num: T::from_u128(num),
+                denom: T::from_u128(denom),
+            };
+            let decrypted = crypto_param.decrypt(crypto_param.encrypt(message.clone()));
+            assert_eq!(decrypted, message);
+        }
+        let zero = crypto_param.decrypt(hensel_code::HenselCode::generate_zero(T::from_u128(
+            7919 * 37 * 41 * 5897 * 7759,
+        )));
+        assert_eq!(zero.num, T::zero());
+        assert_eq!(zero.denom, T::one());
+    }
+
+    #[test]
+    fn public_key_product_fits() {
+        type T2 = crate::bigint::WrappingCryptoBigInt<2>;
+        let p = |n| T2::from_u128(n);
+        // 4 primes of 31 bits: their product fits in 128 bits
+        let (p1, p2, p3, p4) = (p(2147483647), p(2147483629), p(2147483587), p(2147483579));
+        let e = hensel_code::HenselCode::generate_zero(T2::zero());
+        let public_key = PublicKeySchemeCryptographicParameters::new(p1, p2, p3, p4, 8, e);
+        assert_eq!(
+            public_key.public_key().g,
+            p(2147483647)
+                .mul(&p(2147483629))
+                .mul(&p(2147483587))
+                .mul(&p(2147483579))
+        );
+    }
+
+    #[test]
+    fn prime_product() {
+        use super::EncryptionScheme;
+
+        let crypto_param =
+            PrivateKeySchemeCryptographicParameters::<T>::try_from("7919,37,41,5897,7759").unwrap();
+        // the modulus `delta_max*p4` of the ciphertexts in `encrypt`
+        let delta_max = T::from_u128(7919 * 37 * 41 * 7759);
+        assert_eq!(
+            *crypto_param.prime_product(),
+            delta_max.mul(&T::from_u128(5897))
+        );
+        let ciphertext = crypto_param.encrypt(T::one().into());
+        assert_eq!(ciphertext.modulus, *crypto_param.prime_product());
+    }
+
+    #[test]
+    #[should_panic(expected = "does not fit")]
+    fn private_key_product_overflows() {
+        type T2 = crate::bigint::WrappingCryptoBigInt<2>;
+        let p = |n| T2::from_u128(n);
+        // 5 primes of 31 bits: their product needs 155 bits
+        let primes = [
+            p(2147483647),
+            p(2147483629),
+            p(2147483587),
+            p(2147483579),
+            p(2147483563),
+        ];
+        assert_eq!(
+            PrivateKeySchemeCryptographicParameters::from_primes(&primes).err(),
+            Some(ParamError::ProductOverflow)
+        );
+        let [p1, p2, p3, p4, p5] = primes;
+        PrivateKeySchemeCryptographicParameters::new(p1, p2, p3, p4, p5);
+    }
+
+    #[test]
+    #[should_panic(expected = "does not fit")]
+    fn public_key_product_overflows() {
+        type T2 = crate::bigint::WrappingCryptoBigInt<2>;
+        let p = |n| T2::from_u128(n);
+        // 4 primes of 40 bits: their product needs 160 bits
+        let (p1, p2, p3, p4) = (
+            p(1099511627689),
+            p(1099511627691),
+            p(1099511627711),
+            p(1099511627749),
+        );
+        let e = hensel_code::HenselCode::generate_zero(T2::zero());
+        PublicKeySchemeCryptographicParameters::new(p1, p2, p3, p4, 8, e);
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn traces_encrypt_and_decrypt() {
+        use super::EncryptionScheme;
+        use crate::rational::Rational;
+        use std::sync::{
+            atomic::{AtomicU64, Ordering},
+            Arc, Mutex,
+        };
+        use tracing::{field, span, Event, Metadata, Subscriber};
+
+        /// Records the names of the spans and of the event fields
+        #[derive(Default)]
+        struct Capture {
+            next_id: AtomicU64,
+            spans: Mutex<Vec<String>>,
+            fields: Mutex<Vec<String>>,
+        }
+
+        struct FieldNames<'a>(&'a mut Vec<String>);
+
+        impl field::Visit for FieldNames<'_> {
+            fn record_debug(&mut self, field: &field::Field, _: &dyn std::fmt::Debug) {
+                self.0.push(field.name().to_string());
+            }
+        }
+
+        impl Subscriber for Capture {
+            fn enabled(&self, _: &Metadata) -> bool {
+                true
+            }
+            fn new_span(&self, span: &span::Attributes) -> span::Id {
+                self.spans
+                    .lock()
+                    .unwrap()
+                    .push(span.metadata().name().to_string());
+                span::Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+            }
+            fn record(&self, _: &span::Id, _: &span::Record) {}
+            fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+            fn event(&self, event: &Event) {
+                event.record(&mut FieldNames(&mut self.fields.lock().unwrap()));
+            }
+            fn enter(&self, _: &span::Id) {}
+            fn exit(&self, _: &span::Id) {}
+        }
+
+        let crypto_param =
+            PrivateKeySchemeCryptographicParameters::<T>::try_from("7919,37,41,5897,7759").unwrap();
+        let message = Rational::<T> {
+            num: T::from_u128(10),
+            denom: T::from_u128(3),
+        };
+        let capture = Arc::new(Capture::default());
+        let decrypted = tracing::subscriber::with_default(capture.clone(), || {
+            crypto_param.decrypt(crypto_param.encrypt(message.clone()))
+        });
+        assert_eq!(decrypted, message);
+
+        assert_eq!(*capture.spans.lock().unwrap(), ["encrypt", "decrypt"]);
+        assert_eq!(
+            *capture.fields.lock().unwrap(),
+            [
+                "hc_noise",
+                "r_noise",
+                "rational_term",
+                "hc",
+                "hc_p4",
+                "r_p4",
+                "m"
+            ]
+        );
+    }
+
+    #[test]
+    fn generate_seeded_is_reproducible() {
+        let primes = |seed| {
+            PrivateKeySchemeCryptographicParameters::<T>::generate_seeded(8, 0, seed)
+                .primes()
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+        let key = primes(42);
+        assert_eq!(key, primes(42));
+        assert_ne!(key, primes(43));
+        assert!(crate::bigint::are_pairwise_coprime(&key));
+    }
+
+    #[test]
+    fn generate_with_config() {
+        use super::KeyGenConfig;
+
+        assert_eq!(
+            KeyGenConfig::default().prime_bits,
+            KeyGenConfig::from_security(8, 1).prime_bits
+        );
+        let cfg = KeyGenConfig {
+            prime_bits: [17, 6, 7, 40, 64],
+            seed: Some(7),
+        };
+        let key = PrivateKeySchemeCryptographicParameters::<T>::generate_with_config(&cfg).unwrap();
+        for (p, bits) in key.primes().zip(&cfg.prime_bits) {
+            assert!(*p >= T::from_u128(1 << (bits - 1)) && *p < T::from_u128(1 << bits));
+        }
+        let same_key =
+            PrivateKeySchemeCryptographicParameters::<T>::generate_with_config(&cfg).unwrap();
+        assert!(key.primes().eq(same_key.primes()));
+
+        // 5 and 7 are the only 3-bit primes
+        type T4 = crate::bigint::WrappingCryptoBigInt<4>;
+        let too_few = KeyGenConfig {
+            prime_bits: [3, 3, 3, 40, 64],
+            seed: None,
+        };
+        assert_eq!(
+            PrivateKeySchemeCryptographicParameters::<T4>::generate_with_config(&too_few).err(),
+            Some(ParamError::NotEnoughPrimes(3))
+        );
+
+        // the product of the primes is at least 2^(134 - 5), over 128 bits
+        type T128 = crate::bigint::WrappingCryptoBigInt<{ crate::shared::limbs_for_bits(128) }>;
+        assert_eq!(
+            PrivateKeySchemeCryptographicParameters::<T128>::generate_with_config(&cfg).err(),
+            Some(ParamError::ProductOverflow)
+        );
+    }
+
+    #[test]
+    fn generate_bounded() {
+        use super::KeyGenConfig;
+        type T2 = crate::bigint::WrappingCryptoBigInt<2>;
+
+        // 128 bits fit in 2 limbs, 129 bits do not
+        let fitting = KeyGenConfig {
+            prime_bits: [17, 6, 7, 40, 58],
+            seed: Some(7),
+        };
+        assert!(PrivateKeySchemeCryptographicParameters::<T2>::generate_bounded(&fitting).is_ok());
+        let oversized = KeyGenConfig {
+            prime_bits: [17, 6, 7, 40, 59],
+            seed: Some(7),
+        };
+        assert_eq!(
+            PrivateKeySchemeCryptographicParameters::<T2>::generate_bounded(&oversized).err(),
+            Some(ParamError::ProductOverflow)
+        );
+    }
+
+    #[cfg(feature = "debug-internals")]
+    #[test]
+    fn encrypt_with_report() {
+        use super::EncryptionScheme;
+        use crate::rational::Rational;
+
+        let crypto_param =
+            PrivateKeySchemeCryptographicParameters::<T>::try_from("7919,37,41,5897,7759").unwrap();
+        let message = Rational::<T> {
+            num: T::from_u128(10),
+            denom: T::from_u128(3),
+        };
+        let (hc, noise) = crypto_param.encrypt_with_report(message.clone());
+        assert_eq!(noise.delta_max, T::from_u128(7919 * 37 * 41 * 7759));
+        assert!(noise.delta < noise.delta_max);
+        assert!(noise.s1 < T::from_u128(7919));
+        assert!(noise.s2 < T::from_u128(37));
+        assert!(noise.s3 < T::from_u128(41));
+        assert_eq!(crypto_param.decrypt(hc), message);
+    }
 }
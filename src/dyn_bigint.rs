@@ -0,0 +1,221 @@
+use super::{convert::TryFrom, fmt};
+use crate::{
+    bigint::{BigIntTrait, WrappingCryptoBigInt},
+    shared::DEFAULT_LIMBS,
+};
+
+/// Big int whose number of limbs is chosen at runtime among a few common sizes, for
+/// applications which do not know `L` at compile time. Arithmetic is dispatched to the
+/// underlying `WrappingCryptoBigInt<L>`; both operands must have the same size.
+// the big ints are stored inline, as `WrappingCryptoBigInt<L>` itself is
+#[allow(clippy::large_enum_variant)]
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum DynBigInt {
+    L4(WrappingCryptoBigInt<4>),
+    L16(WrappingCryptoBigInt<16>),
+    L64(WrappingCryptoBigInt<64>),
+    Default(WrappingCryptoBigInt<DEFAULT_LIMBS>),
+}
+
+/// Error returned when converting a DynBigInt into a big int of another size
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct SizeMismatch {
+    pub expected: usize,
+    pub found: usize,
+}
+
+impl fmt::Display for SizeMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "expected a {}-limb big int, found {} limbs",
+            self.expected, self.found
+        )
+    }
+}
+
+impl std::error::Error for SizeMismatch {}
+
+/// Evaluates `$e` on the underlying big int `$n_` of `$n`, wrapping the result back
+macro_rules! dispatch_unary {
+    ($n: expr, $n_: ident => $e: expr) => {
+        match $n {
+            DynBigInt::L4($n_) => DynBigInt::L4($e),
+            DynBigInt::L16($n_) => DynBigInt::L16($e),
+            DynBigInt::L64($n_) => DynBigInt::L64($e),
+            DynBigInt::Default($n_) => DynBigInt::Default($e),
+        }
+    };
+}
+
+/// Applies the binary `$method` to the underlying big ints of `$a` and `$b`, panics if their
+/// sizes differ
+macro_rules! dispatch_binary {
+    ($a: expr, $b: expr, $method: ident) => {
+        match ($a, $b) {
+            (DynBigInt::L4(a), DynBigInt::L4(b)) => DynBigInt::L4(a.$method(b)),
+            (DynBigInt::L16(a), DynBigInt::L16(b)) => DynBigInt::L16(a.$method(b)),
+            (DynBigInt::L64(a), DynBigInt::L64(b)) => DynBigInt::L64(a.$method(b)),
+            (DynBigInt::Default(a), DynBigInt::Default(b)) => DynBigInt::Default(a.$method(b)),
+            (a, b) => panic!(
+                "cannot {} '{}' ({} limbs) and '{}' ({} limbs)",
+                stringify!($method),
+                a,
+                a.limbs(),
+                b,
+                b.limbs()
+            ),
+        }
+    };
+}
+
+impl DynBigInt {
+    /// Number of limbs of the supported sizes
+    pub const SIZES: [usize; 4] = [4, 16, 64, DEFAULT_LIMBS];
+
+    /// Casts `n` into a big int of `limbs` limbs, or `None` if that size is not supported
+    pub fn from_u128(limbs: usize, n: u128) -> Option<Self> {
+        match limbs {
+            4 => Some(DynBigInt::L4(WrappingCryptoBigInt::from_u128(n))),
+            16 => Some(DynBigInt::L16(WrappingCryptoBigInt::from_u128(n))),
+            64 => Some(DynBigInt::L64(WrappingCryptoBigInt::from_u128(n))),
+            DEFAULT_LIMBS => Some(DynBigInt::Default(WrappingCryptoBigInt::from_u128(n))),
+            _ => None,
+        }
+    }
+
+    /// Returns the number of limbs of the big int
+    pub fn limbs(&self) -> usize {
+        match self {
+            DynBigInt::L4(_) => 4,
+            DynBigInt::L16(_) => 16,
+            DynBigInt::L64(_) => 64,
+            DynBigInt::Default(_) => DEFAULT_LIMBS,
+        }
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        dispatch_binary!(self, other, add)
+    }
+
+    pub fn sub(&self, other: &Self) -> Self {
+        dispatch_binary!(self, other, sub)
+    }
+
+    pub fn mul(&self, other: &Self) -> Self {
+        dispatch_binary!(self, other, mul)
+    }
+
+    pub fn div(&self, other: &Self) -> Self {
+        dispatch_binary!(self, other, div)
+    }
+
+    pub fn rem(&self, other: &Self) -> Self {
+        dispatch_binary!(self, other, rem)
+    }
+
+    pub fn gcd(&self, other: &Self) -> Self {
+        dispatch_binary!(self, other, gcd)
+    }
+
+    pub fn pow(&self, exponent: u128) -> Self {
+        dispatch_unary!(self, n => n.pow(exponent))
+    }
+
+    pub fn is_zero(&self) -> bool {
+        match self {
+            DynBigInt::L4(n) => n.is_zero(),
+            DynBigInt::L16(n) => n.is_zero(),
+            DynBigInt::L64(n) => n.is_zero(),
+            DynBigInt::Default(n) => n.is_zero(),
+        }
+    }
+}
+
+/// Displays the underlying big int
+impl fmt::Display for DynBigInt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DynBigInt::L4(n) => fmt::Display::fmt(n, f),
+            DynBigInt::L16(n) => fmt::Display::fmt(n, f),
+            DynBigInt::L64(n) => fmt::Display::fmt(n, f),
+            DynBigInt::Default(n) => fmt::Display::fmt(n, f),
+        }
+    }
+}
+
+/// Implements the conversions between DynBigInt and the big int of each supported size
+macro_rules! impl_dyn_big_int_conversions {
+    ($(($variant: ident, $limbs: expr)),+) => {
+        $(
+            impl From<WrappingCryptoBigInt<$limbs>> for DynBigInt {
+                fn from(n: WrappingCryptoBigInt<$limbs>) -> Self {
+                    DynBigInt::$variant(n)
+                }
+            }
+
+            impl TryFrom<DynBigInt> for WrappingCryptoBigInt<$limbs> {
+                type Error = SizeMismatch;
+                fn try_from(n: DynBigInt) -> Result<Self, Self::Error> {
+                    match n {
+                        DynBigInt::$variant(n) => Ok(n),
+                        n => Err(SizeMismatch {
+                            expected: $limbs,
+                            found: n.limbs(),
+                        }),
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_dyn_big_int_conversions!((L4, 4), (L16, 16), (L64, 64), (Default, DEFAULT_LIMBS));
+
+#[cfg(test)]
+mod tests {
+    use super::{DynBigInt, SizeMismatch};
+    use crate::bigint::{BigIntTrait, WrappingCryptoBigInt};
+    use std::convert::TryFrom;
+
+    #[test]
+    fn arithmetic_at_runtime_sizes() {
+        for limbs in [4, 64] {
+            let n = |n| DynBigInt::from_u128(limbs, n).unwrap();
+            let (a, b) = (n(7919 * 12), n(7919 * 18));
+            assert_eq!(a.limbs(), limbs);
+            assert_eq!(a.add(&b), n(7919 * 30));
+            assert_eq!(b.sub(&a), n(7919 * 6));
+            assert_eq!(a.mul(&b), n(7919 * 7919 * 216));
+            assert_eq!(b.div(&n(7919)), n(18));
+            assert_eq!(b.rem(&n(1000)), n(7919 * 18 % 1000));
+            assert_eq!(a.gcd(&b), n(7919 * 6));
+            assert_eq!(n(2).pow(100), n(1 << 100));
+            assert!(a.sub(&a).is_zero());
+        }
+        assert_eq!(DynBigInt::from_u128(3, 1), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot add")]
+    fn mixed_sizes() {
+        let a = DynBigInt::from_u128(4, 1).unwrap();
+        let b = DynBigInt::from_u128(16, 1).unwrap();
+        a.add(&b);
+    }
+
+    #[test]
+    fn conversions() {
+        let n = WrappingCryptoBigInt::<16>::from_u128(1337);
+        let dyn_n = DynBigInt::from(n.clone());
+        assert_eq!(dyn_n.to_string(), n.to_string());
+        assert_eq!(WrappingCryptoBigInt::<16>::try_from(dyn_n.clone()), Ok(n));
+        assert_eq!(
+            WrappingCryptoBigInt::<4>::try_from(dyn_n),
+            Err(SizeMismatch {
+                expected: 4,
+                found: 16
+            })
+        );
+    }
+}
@@ -1,11 +1,23 @@
 use super::{
+    convert::TryFrom,
     fmt,
-    ops::{Add, Mul},
+    ops::{Add, Mul, Neg},
     rational::Rational,
 };
-use crate::bigint::BigIntTrait;
+use crate::bigint::{be_bytes_to_u128, BigIntTrait, U128Overflow, WrappingCryptoBigInt};
+use crypto_bigint::{
+    modular::runtime_mod::{DynResidue, DynResidueParams},
+    Integer, Wrapping,
+};
+use rand::{CryptoRng, RngCore};
+
+/// Window of the non-adjacent form scanned by `HenselCode::pow` for long exponents
+const NAF_WINDOW: usize = 4;
+
+/// Length in bytes above which `HenselCode::pow` scans the non-adjacent form of the exponent
+const NAF_MIN_BYTES: usize = 16;
 
-#[derive(Clone, Debug)]
+#[derive(Clone)]
 pub struct HenselCode<T: BigIntTrait> {
     pub modulus: T,
     pub res: T, // internal variable that stores the residue
@@ -17,6 +29,65 @@ impl<T: BigIntTrait> HenselCode<T> {
             res: T::zero(),
         }
     }
+
+    /// Builds a HenselCode from its modulus and residue, rejecting a residue which is not in
+    /// `[0, modulus)` instead of reducing it, e.g. to validate deserialized data
+    pub fn try_from_parts(modulus: &T, residue: &T) -> Result<HenselCode<T>, ResidueTooLarge> {
+        new_hensel_code_checked(modulus, residue)
+    }
+
+    /// Samples a uniformly random HenselCode modulo `modulus`
+    pub fn random(modulus: &T) -> HenselCode<T> {
+        HenselCode {
+            modulus: modulus.clone(),
+            res: T::random_mod(modulus),
+        }
+    }
+
+    /// Same as `random`, drawing the randomness from `rng`
+    pub fn random_with_rng<R: RngCore + CryptoRng>(modulus: &T, rng: &mut R) -> HenselCode<T> {
+        HenselCode {
+            modulus: modulus.clone(),
+            res: T::random_mod_with_rng(modulus, rng),
+        }
+    }
+}
+
+/// Builds a HenselCode from an arbitrary modulus, replaced by one if it is zero, and an arbitrary
+/// residue reduced modulo it
+#[cfg(feature = "arbitrary")]
+impl<'a, T: BigIntTrait + arbitrary::Arbitrary<'a>> arbitrary::Arbitrary<'a> for HenselCode<T> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let modulus = T::arbitrary(u)?;
+        let modulus = if modulus.is_zero() { T::one() } else { modulus };
+        let res = T::arbitrary(u)?;
+        Ok(new_hensel_code(&modulus, &res))
+    }
+}
+
+/// Escape hatch to crypto-bigint's Montgomery residues, for modular arithmetic not covered by
+/// HenselCode. Nothing checks what is done to the residue in between.
+impl<const L: usize> HenselCode<WrappingCryptoBigInt<L>> {
+    /// Converts the HenselCode into a crypto-bigint residue, or `None` if the modulus is even
+    pub fn to_residue(&self) -> Option<DynResidue<L>> {
+        let modulus = self.modulus.0 .0;
+        if bool::from(modulus.is_odd()) {
+            Some(DynResidue::new(
+                &self.res.0 .0,
+                DynResidueParams::new(&modulus),
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// Wraps a crypto-bigint residue back into a HenselCode
+    pub fn from_residue(residue: DynResidue<L>) -> Self {
+        HenselCode {
+            modulus: WrappingCryptoBigInt(Wrapping(*residue.params().modulus())),
+            res: WrappingCryptoBigInt(Wrapping(residue.retrieve())),
+        }
+    }
 }
 
 /// Creates an HenselCode from two BigInt
@@ -27,35 +98,266 @@ pub fn new_hensel_code<T: BigIntTrait>(modulus: &T, n: &T) -> HenselCode<T> {
     }
 }
 
+/// Extracts the residue of a HenselCode as a `u128`, failing if it does not fit
+impl<T: BigIntTrait> TryFrom<&HenselCode<T>> for u128 {
+    type Error = U128Overflow;
+    fn try_from(hc: &HenselCode<T>) -> Result<Self, Self::Error> {
+        be_bytes_to_u128(&hc.res.to_be_bytes())
+    }
+}
+
+/// Error returned by `new_hensel_code_checked` and `HenselCode::try_from_parts` when the residue
+/// is not in `[0, modulus)`
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct ResidueTooLarge;
+
+impl fmt::Display for ResidueTooLarge {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "residue is not in [0, modulus)")
+    }
+}
+
+impl std::error::Error for ResidueTooLarge {}
+
+/// Same as `new_hensel_code`, returning an error instead of reducing `n` when it is not in
+/// `[0, modulus)`, for callers which expect `n` to be reduced already
+pub fn new_hensel_code_checked<T: BigIntTrait>(
+    modulus: &T,
+    n: &T,
+) -> Result<HenselCode<T>, ResidueTooLarge> {
+    if *n < T::zero() || n >= modulus {
+        return Err(ResidueTooLarge);
+    }
+    Ok(new_hensel_code(modulus, n))
+}
+
+/// Creates the HenselCodes of all the big ints in `ns` modulo `g`. For an odd `g`, the
+/// Montgomery parameters are computed once and shared by all the reductions.
+pub fn new_hensel_codes<const L: usize>(
+    g: &WrappingCryptoBigInt<L>,
+    ns: &[WrappingCryptoBigInt<L>],
+) -> Vec<HenselCode<WrappingCryptoBigInt<L>>> {
+    if !bool::from(g.0 .0.is_odd()) {
+        return ns.iter().map(|n| new_hensel_code(g, n)).collect();
+    }
+    let params = DynResidueParams::new(&g.0 .0);
+    ns.iter()
+        .map(|n| HenselCode {
+            modulus: g.clone(),
+            res: WrappingCryptoBigInt(Wrapping(DynResidue::new(&n.0 .0, params).retrieve())),
+        })
+        .collect()
+}
+
 impl<T: BigIntTrait> HenselCode<T> {
-    pub fn invert(&self) -> HenselCode<T> {
-        let g = self.modulus.clone();
-        let (mut x0, mut x1) = (self.modulus.clone(), self.res.clone());
-        let (mut y0, mut y1) = (T::zero(), T::one());
-        while !x1.is_zero() {
-            let integer_div = x0.div(&x1);
-            (x0, x1) = (x1.clone(), x0.sub(&integer_div.mul(&x1)));
-            (y0, y1) = (
-                y1.clone(),
-                y0.add(&g).sub(&integer_div.mul(&y1).rem(&g)).rem(&g),
-            );
+    /// Inverts the residue with `T::gcd_ext`. Returns whether the residue is a unit, and its
+    /// inverse if it is (a meaningless value otherwise), without branching on the outcome.
+    fn extended_inverse(&self) -> (bool, T) {
+        let (gcd, inverse) = T::gcd_ext(&self.res, &self.modulus);
+        (gcd == T::one(), inverse)
+    }
+
+    /// Returns the balanced representative of the residue in `(-modulus/2, modulus/2]`, as
+    /// whether it is negative and its absolute value
+    pub fn to_signed_bigint(&self) -> (bool, T) {
+        let half = self.modulus.div(&T::from_u128(2));
+        if self.res > half {
+            (true, self.modulus.sub(&self.res))
+        } else {
+            (false, self.res.clone())
         }
-        // we have:
-        // x0 = gcd(modulus, res) = (y0 * res) % modulus
-        // x1 = 0
-        if x0 != T::one() {
-            HenselCode {
+    }
+
+    /// Checks whether the residue is invertible modulo `modulus`, i.e. `gcd(res, modulus) = 1`
+    pub fn is_unit(&self) -> bool {
+        self.res.gcd(&self.modulus) == T::one()
+    }
+
+    /// Returns the inverse of the HenselCode, or `None` if the residue is not invertible modulo
+    /// `modulus`
+    pub fn try_invert(&self) -> Option<HenselCode<T>> {
+        let (is_unit, inverse) = self.extended_inverse();
+        if is_unit {
+            Some(HenselCode {
                 modulus: self.modulus.clone(),
-                res: T::zero(),
+                res: inverse,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Moves the HenselCode to `new_modulus`, keeping the canonical residue in `[0, modulus)`.
+    /// When `new_modulus` divides `modulus` this is the reduction map, so sums and products are
+    /// preserved. When `new_modulus` is larger the residue is kept unchanged, which is only a
+    /// lift of the canonical representative: it is not compatible with the arithmetic.
+    pub fn change_modulus(&self, new_modulus: &T) -> HenselCode<T> {
+        new_hensel_code(new_modulus, &self.res)
+    }
+
+    /// Combines two codes of the same rational modulo powers `p^a` and `p^b` of the same prime,
+    /// checking that the lower-precision code agrees with the higher-precision one, which is
+    /// returned. Panics if the smaller modulus does not divide the larger one.
+    pub fn combine_with_precision(
+        &self,
+        other: &HenselCode<T>,
+    ) -> Result<HenselCode<T>, Inconsistent> {
+        let (low, high) = if self.modulus <= other.modulus {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        if !high.modulus.rem(&low.modulus).is_zero() {
+            panic!(
+                "cannot combine codes modulo '{}' and '{}', which are not powers of the same prime",
+                low.modulus, high.modulus
+            )
+        }
+        if high.res.rem(&low.modulus) != low.res {
+            return Err(Inconsistent);
+        }
+        Ok(high.clone())
+    }
+
+    /// Reduces the stored residue modulo `new_prime`. Unlike `change_modulus`, the prime is not
+    /// expected to be related to the current modulus, so the result generally represents a
+    /// different rational than `self`.
+    pub fn reinterpret(&self, new_prime: &T) -> HenselCode<T> {
+        new_hensel_code(new_prime, &self.res)
+    }
+
+    /// Applies `f` to the residue, reducing the result modulo `modulus`
+    pub fn map_residue<F: Fn(T) -> T>(&self, f: F) -> HenselCode<T> {
+        new_hensel_code(&self.modulus, &f(self.res.clone()))
+    }
+
+    /// Adds the plain integer `k` to the HenselCode
+    pub fn add_scalar(&self, k: &T) -> HenselCode<T> {
+        HenselCode {
+            modulus: self.modulus.clone(),
+            res: self.res.add(&k.rem(&self.modulus)).rem(&self.modulus),
+        }
+    }
+
+    /// Multiplies the HenselCode by the plain integer `k`
+    pub fn mul_scalar(&self, k: &T) -> HenselCode<T> {
+        HenselCode {
+            modulus: self.modulus.clone(),
+            res: self.res.mul(&k.rem(&self.modulus)).rem(&self.modulus),
+        }
+    }
+
+    /// Returns the base-`p` digits of the residue, least significant first. As many digits as
+    /// needed to write any residue modulo `modulus` are returned, so a modulus `p^k` gives the
+    /// first `k` digits of the p-adic expansion.
+    pub fn digits(&self, p: &T) -> Vec<T> {
+        let (mut n, mut m) = (self.res.clone(), self.modulus.clone());
+        let mut digits = Vec::new();
+        while m > T::one() {
+            digits.push(n.rem(p));
+            n = n.div(p);
+            m = m.sub(&T::one()).div(p).add(&T::one());
+        }
+        digits
+    }
+
+    /// Returns an iterator over the base-`p` digits of the residue, computed lazily
+    pub fn digit_iter<'a>(&self, p: &'a T) -> PadicDigits<'a, T> {
+        PadicDigits {
+            p,
+            n: self.res.clone(),
+            m: self.modulus.clone(),
+        }
+    }
+
+    /// Returns the inverse of the HenselCode, panics if the residue is not invertible
+    pub fn invert(&self) -> HenselCode<T> {
+        match self.try_invert() {
+            Some(inverse) => inverse,
+            None => panic!("cannot invert '{}'", self),
+        }
+    }
+
+    /// Raises the HenselCode to the power `exponent`, by square-and-multiply, or with `pow_naf`
+    /// for exponents longer than `NAF_MIN_BYTES` bytes
+    pub fn pow(&self, exponent: &T) -> HenselCode<T> {
+        if exponent.to_be_bytes().len() > NAF_MIN_BYTES {
+            self.pow_naf(exponent, NAF_WINDOW)
+        } else {
+            self.pow_binary(exponent)
+        }
+    }
+
+    /// Raises the HenselCode to the power `exponent`, scanning its bits by square-and-multiply
+    fn pow_binary(&self, exponent: &T) -> HenselCode<T> {
+        let mut result = new_hensel_code(&self.modulus, &T::one());
+        for byte in exponent.to_be_bytes() {
+            for i in (0..8).rev() {
+                result = &result * &result;
+                if (byte >> i) & 1 == 1 {
+                    result = &result * self;
+                }
+            }
+        }
+        result
+    }
+
+    /// Same as `pow`, scanning the width-`window` non-adjacent form of `exponent`, which needs
+    /// fewer multiplications. Negative digits multiply by powers of the inverse, so when they
+    /// occur and the residue is not invertible, this falls back to square-and-multiply.
+    pub fn pow_naf(&self, exponent: &T, window: usize) -> HenselCode<T> {
+        let naf = exponent.to_naf(window);
+        // the odd powers x, x^3, ..., x^(2^(window - 1) - 1)
+        let odd_powers = |x: &HenselCode<T>| {
+            let x2 = x * x;
+            let mut powers = vec![x.clone()];
+            for _ in 1..1 << (window - 2) {
+                let next = powers.last().unwrap() * &x2;
+                powers.push(next);
+            }
+            powers
+        };
+        let inverse_powers = if naf.iter().any(|d| *d < 0) {
+            match self.try_invert() {
+                Some(inverse) => odd_powers(&inverse),
+                None => return self.pow_binary(exponent),
             }
         } else {
-            HenselCode {
-                modulus: self.modulus.clone(),
-                res: y0,
+            Vec::new()
+        };
+        let powers = odd_powers(self);
+        let mut result = new_hensel_code(&self.modulus, &T::one());
+        for d in naf.iter().rev() {
+            result = &result * &result;
+            if *d > 0 {
+                result = &result * &powers[(*d as usize - 1) / 2];
+            } else if *d < 0 {
+                result = &result * &inverse_powers[(d.unsigned_abs() as usize - 1) / 2];
             }
         }
+        result
+    }
+}
+/// Iterator over the base-`p` digits of a HenselCode, least significant first
+pub struct PadicDigits<'a, T: BigIntTrait> {
+    p: &'a T,
+    n: T, // remaining part of the residue
+    m: T, // remaining part of the modulus
+}
+
+impl<T: BigIntTrait> Iterator for PadicDigits<'_, T> {
+    type Item = T;
+    fn next(&mut self) -> Option<T> {
+        if self.m <= T::one() {
+            return None;
+        }
+        let digit = self.n.rem(self.p);
+        self.n = self.n.div(self.p);
+        self.m = self.m.sub(&T::one()).div(self.p).add(&T::one());
+        Some(digit)
     }
 }
+
 /// Adds two HenselCodes
 impl<T: BigIntTrait> Add<HenselCode<T>> for HenselCode<T> {
     type Output = HenselCode<T>;
@@ -72,19 +374,22 @@ impl<T: BigIntTrait> Mul<HenselCode<T>> for HenselCode<T> {
 }
 
 /// Adds two &HenselCodes
+#[allow(clippy::needless_lifetimes)]
 impl<'a, 'b, T: BigIntTrait> Add<&'b HenselCode<T>> for &'a HenselCode<T> {
     type Output = HenselCode<T>;
     fn add(self, other: &'b HenselCode<T>) -> HenselCode<T> {
         if self.modulus != other.modulus {
             panic!("cannot add '{}' and '{}'", self, other);
         }
+        // both residues are reduced, so their sum is below `2*modulus`
         HenselCode {
             modulus: self.modulus.clone(),
-            res: self.res.add(&other.res).rem(&self.modulus),
+            res: self.res.add(&other.res).reduce_once(&self.modulus),
         }
     }
 }
 /// Multiplies two &HenselCodes
+#[allow(clippy::needless_lifetimes)]
 impl<'a, 'b, T: BigIntTrait> Mul<&'b HenselCode<T>> for &'a HenselCode<T> {
     type Output = HenselCode<T>;
     fn mul(self, other: &'b HenselCode<T>) -> HenselCode<T> {
@@ -98,28 +403,329 @@ impl<'a, 'b, T: BigIntTrait> Mul<&'b HenselCode<T>> for &'a HenselCode<T> {
     }
 }
 
+/// Negates a &HenselCode, i.e. returns `modulus - res` (and zero for zero)
+impl<T: BigIntTrait> Neg for &HenselCode<T> {
+    type Output = HenselCode<T>;
+    fn neg(self) -> HenselCode<T> {
+        HenselCode {
+            modulus: self.modulus.clone(),
+            res: self.res.neg_mod(&self.modulus),
+        }
+    }
+}
+/// Negates a HenselCode
+impl<T: BigIntTrait> Neg for HenselCode<T> {
+    type Output = HenselCode<T>;
+    fn neg(self) -> HenselCode<T> {
+        -&self
+    }
+}
+
 pub fn chinese_remainder<T: BigIntTrait>(hc1: HenselCode<T>, hc2: HenselCode<T>) -> HenselCode<T> {
     let (g1, n1) = (hc1.modulus, hc1.res);
     let (g2, n2) = (hc2.modulus, hc2.res);
     // println!("\ng1: {g1}, g2: {g2}, gcd: {}", g1.gcd(&g2));
-    assert!(PartialEq::eq(&g1.gcd(&g2), &T::one()));
     let g12 = g1.mul(&g2);
-    let (g1_mod_g2, g2_mod_g1) = (
-        HenselCode {
-            modulus: g2.clone(),
-            res: g1.clone(),
-        },
-        HenselCode {
-            modulus: g1.clone(),
-            res: g2.clone(),
-        },
-    );
     // i1*g1 = 1 (mod g2), i2*g2 = 1 (mod g1)
-    let (i1, i2) = (g1_mod_g2.invert().res, g2_mod_g1.invert().res);
+    let (i1, i2) = match (T::gcd_inv(&g1, &g2), T::gcd_inv(&g2, &g1)) {
+        ((_, Some(i1)), (_, Some(i2))) => (i1, i2),
+        ((gcd, _), _) => panic!(
+            "cannot combine moduli '{}' and '{}' (gcd '{}')",
+            g1, g2, gcd
+        ),
+    };
 
     new_hensel_code(&g12, &g1.mul(&i1).mul(&n2).add(&g2.mul(&i2).mul(&n1)))
 }
 
+/// Same as `chinese_remainder`, returning `None` instead of wrapping if the intermediate values
+/// do not fit in `T`. It computes `n1 + g1*t` with `t = (n2 - n1)*g1^-1 (mod g2)`, so only
+/// `g2^2` and `g1*g2` need to fit in `T`.
+pub fn checked_chinese_remainder<T: BigIntTrait>(
+    hc1: HenselCode<T>,
+    hc2: HenselCode<T>,
+) -> Option<HenselCode<T>> {
+    let (g1, n1) = (hc1.modulus, hc1.res);
+    let (g2, n2) = (hc2.modulus, hc2.res);
+    let g12 = g1.checked_mul(&g2)?;
+    let i1 = match T::gcd_inv(&g1, &g2) {
+        (_, Some(i1)) => i1,
+        (gcd, None) => panic!(
+            "cannot combine moduli '{}' and '{}' (gcd '{}')",
+            g1, g2, gcd
+        ),
+    };
+    let diff = n2.add(&g2).sub(&n1.rem(&g2)).rem(&g2);
+    let t = diff.checked_mul(&i1)?.rem(&g2);
+    // n1 < g1 and t < g2, so n1 + g1*t < g1*g2
+    let res = n1.checked_add(&g1.checked_mul(&t)?)?;
+    Some(HenselCode { modulus: g12, res })
+}
+
+/// Same as `chinese_remainder`, returning the combined HenselCode at `LG` limbs so that the
+/// product of the moduli does not overflow `L` limbs. The intermediate products are reduced
+/// modulo `g1` or `g2`, so they fit in `LG` limbs; panics if `LG < 2*L`.
+pub fn chinese_remainder_widen<const L: usize, const LG: usize>(
+    hc1: HenselCode<WrappingCryptoBigInt<L>>,
+    hc2: HenselCode<WrappingCryptoBigInt<L>>,
+) -> HenselCode<WrappingCryptoBigInt<LG>> {
+    if LG < 2 * L {
+        panic!("cannot combine {}-limb Hensel codes into {} limbs", L, LG);
+    }
+    let (g1, n1) = (hc1.modulus.resize::<LG>(), hc1.res.resize::<LG>());
+    let (g2, n2) = (hc2.modulus.resize::<LG>(), hc2.res.resize::<LG>());
+    assert!(PartialEq::eq(&g1.gcd(&g2), &BigIntTrait::one()));
+    let g12 = BigIntTrait::mul(&g1, &g2);
+    // i1*g1 = 1 (mod g2), i2*g2 = 1 (mod g1)
+    let i1 = new_hensel_code(&g2, &g1).invert();
+    let i2 = new_hensel_code(&g1, &g2).invert();
+    // both terms are below g1*g2, add them modulo g1*g2 without exceeding it
+    let term1 = BigIntTrait::mul(&g1, &(i1 * new_hensel_code(&g2, &n2)).res);
+    let term2 = BigIntTrait::mul(&g2, &(i2 * new_hensel_code(&g1, &n1)).res);
+    let complement = BigIntTrait::sub(&g12, &term2);
+    let res = if term1 >= complement {
+        BigIntTrait::sub(&term1, &complement)
+    } else {
+        BigIntTrait::add(&term1, &term2)
+    };
+    HenselCode { modulus: g12, res }
+}
+
+/// Error returned when two HenselCodes disagree modulo the common factor of their moduli
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Incompatible;
+
+impl fmt::Display for Incompatible {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "residues disagree modulo the gcd of the moduli")
+    }
+}
+
+impl std::error::Error for Incompatible {}
+
+/// Error returned when a lower-precision HenselCode disagrees with a higher-precision one
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Inconsistent;
+
+impl fmt::Display for Inconsistent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "lower-precision residue disagrees with the higher-precision one"
+        )
+    }
+}
+
+impl std::error::Error for Inconsistent {}
+
+/// Chinese remainder theorem for moduli which are not necessarily coprime. With `g = gcd(g1, g2)`,
+/// the residues must agree modulo `g`, and the result is the unique residue modulo
+/// `lcm(g1, g2) = g1*(g2/g)` reducing to both of them.
+pub fn chinese_remainder_coprime_split<T: BigIntTrait>(
+    hc1: HenselCode<T>,
+    hc2: HenselCode<T>,
+) -> Result<HenselCode<T>, Incompatible> {
+    let (g1, n1) = (hc1.modulus, hc1.res);
+    let (g2, n2) = (hc2.modulus, hc2.res);
+    let g = g1.gcd(&g2);
+    if n1.rem(&g) != n2.rem(&g) {
+        return Err(Incompatible);
+    }
+    // x = n1 + g1*t with t = ((n2 - n1)/g) * (g1/g)^-1 (mod g2/g), where g1/g and g2/g are coprime
+    let g2_over_g = g2.div(&g);
+    let diff = n2.add(&g2).sub(&n1.rem(&g2)).rem(&g2).div(&g);
+    let inverse = new_hensel_code(&g2_over_g, &g1.div(&g)).invert();
+    let t = (new_hensel_code(&g2_over_g, &diff) * inverse).res;
+    Ok(new_hensel_code(&g1.mul(&g2_over_g), &n1.add(&g1.mul(&t))))
+}
+
+/// Evaluates the polynomial `coeffs[0] + coeffs[1]*x + ... + coeffs[n]*x^n` at `x` using
+/// Horner's method. All the coefficients must share the modulus of `x`.
+pub fn poly_eval<T: BigIntTrait>(coeffs: &[HenselCode<T>], x: &HenselCode<T>) -> HenselCode<T> {
+    let mut result = HenselCode::generate_zero(x.modulus.clone());
+    for coeff in coeffs.iter().rev() {
+        if coeff.modulus != x.modulus {
+            panic!("cannot evaluate '{}' at '{}'", coeff, x);
+        }
+        result = &(&result * x) + coeff;
+    }
+    result
+}
+
+/// Error returned when decoding a HenselCode from its transport bytes fails
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum TransportError {
+    Truncated,
+    TrailingBytes,
+    Overflow,
+    ZeroModulus,
+    ResidueOutOfRange,
+}
+
+impl fmt::Display for TransportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TransportError::Truncated => write!(f, "truncated Hensel code bytes"),
+            TransportError::TrailingBytes => write!(f, "trailing bytes after the Hensel code"),
+            TransportError::Overflow => write!(f, "Hensel code too large for the big int type"),
+            TransportError::ZeroModulus => write!(f, "zero Hensel code modulus"),
+            TransportError::ResidueOutOfRange => {
+                write!(f, "Hensel code residue not in [0, modulus)")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TransportError {}
+
+/// Reads a field prefixed by its length (4 bytes, big-endian) from the start of `bytes`,
+/// returning the field and the remaining bytes
+fn read_length_prefixed(bytes: &[u8]) -> Result<(&[u8], &[u8]), TransportError> {
+    if bytes.len() < 4 {
+        return Err(TransportError::Truncated);
+    }
+    let (length, rest) = bytes.split_at(4);
+    let length = u32::from_be_bytes([length[0], length[1], length[2], length[3]]) as usize;
+    if rest.len() < length {
+        return Err(TransportError::Truncated);
+    }
+    Ok(rest.split_at(length))
+}
+
+impl<T: BigIntTrait> HenselCode<T> {
+    /// Serializes the modulus and the residue, each as big-endian bytes prefixed by their length
+    /// (4 bytes, big-endian)
+    pub fn to_transport_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        for n in [&self.modulus, &self.res] {
+            let n_bytes = n.to_be_bytes();
+            bytes.extend_from_slice(&(n_bytes.len() as u32).to_be_bytes());
+            bytes.extend_from_slice(&n_bytes);
+        }
+        bytes
+    }
+
+    /// Deserializes a HenselCode written by `to_transport_bytes`, rejecting a zero modulus and a
+    /// residue which is not reduced modulo it
+    pub fn from_transport_bytes(bytes: &[u8]) -> Result<Self, TransportError> {
+        let (modulus, rest) = read_length_prefixed(bytes)?;
+        let (res, rest) = read_length_prefixed(rest)?;
+        if !rest.is_empty() {
+            return Err(TransportError::TrailingBytes);
+        }
+        let modulus = T::from_be_bytes(modulus).ok_or(TransportError::Overflow)?;
+        let res = T::from_be_bytes(res).ok_or(TransportError::Overflow)?;
+        if modulus.is_zero() {
+            return Err(TransportError::ZeroModulus);
+        }
+        Self::try_from_parts(&modulus, &res).map_err(|_| TransportError::ResidueOutOfRange)
+    }
+}
+
+/// Error returned when converting between rationals and HenselCodes fails
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum CodingError {
+    /// the denominator of the rational is not invertible modulo the prime
+    NonInvertibleDenominator,
+    /// no rational within the reconstruction bounds maps to the HenselCode
+    NoReconstruction,
+    /// the decoded integer does not fit in the target type
+    IntegerOverflow,
+}
+
+impl fmt::Display for CodingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CodingError::NonInvertibleDenominator => {
+                write!(f, "denominator is not invertible modulo the prime")
+            }
+            CodingError::NoReconstruction => {
+                write!(
+                    f,
+                    "no rational within the bounds reconstructs the Hensel code"
+                )
+            }
+            CodingError::IntegerOverflow => {
+                write!(f, "decoded integer does not fit in the target type")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CodingError {}
+
+/// Encodes the rational `r` as a HenselCode modulo the prime `p`
+pub fn encode<T: BigIntTrait>(r: &Rational<T>, p: &T) -> Result<HenselCode<T>, CodingError> {
+    if r.denom.is_zero() || p.gcd(&r.denom) != T::one() {
+        return Err(CodingError::NonInvertibleDenominator);
+    }
+    Ok(HenselCode::from((p, r)))
+}
+
+/// Decodes the HenselCode `hc` back to a rational, checking that the reconstructed rational
+/// encodes to `hc`
+pub fn decode<T: BigIntTrait>(hc: &HenselCode<T>) -> Result<Rational<T>, CodingError> {
+    let r = Rational::from(hc);
+    match encode(&r, &hc.modulus) {
+        Ok(reencoded) if reencoded.res == hc.res => Ok(r),
+        _ => Err(CodingError::NoReconstruction),
+    }
+}
+
+/// Plaintext types which can be encoded as a HenselCode modulo a prime
+pub trait Encode<T: BigIntTrait> {
+    fn encode(&self, p: &T) -> Result<HenselCode<T>, CodingError>;
+}
+
+/// Plaintext types which can be decoded from a HenselCode
+pub trait Decode<T: BigIntTrait>: Sized {
+    fn decode(hc: &HenselCode<T>) -> Result<Self, CodingError>;
+}
+
+impl<T: BigIntTrait> Encode<T> for Rational<T> {
+    fn encode(&self, p: &T) -> Result<HenselCode<T>, CodingError> {
+        encode(self, p)
+    }
+}
+
+impl<T: BigIntTrait> Decode<T> for Rational<T> {
+    fn decode(hc: &HenselCode<T>) -> Result<Self, CodingError> {
+        decode(hc)
+    }
+}
+
+/// Integers are encoded by their residue modulo `p`
+impl<T: BigIntTrait> Encode<T> for T {
+    fn encode(&self, p: &T) -> Result<HenselCode<T>, CodingError> {
+        Ok(new_hensel_code(p, self))
+    }
+}
+
+/// Decodes to the canonical residue in `[0, modulus)`
+impl<T: BigIntTrait> Decode<T> for T {
+    fn decode(hc: &HenselCode<T>) -> Result<Self, CodingError> {
+        Ok(hc.res.clone())
+    }
+}
+
+/// Negative integers are encoded as `modulus - |n|`
+impl<T: BigIntTrait> Encode<T> for i64 {
+    fn encode(&self, p: &T) -> Result<HenselCode<T>, CodingError> {
+        let hc = new_hensel_code(p, &T::from_u128(self.unsigned_abs() as u128));
+        Ok(if *self < 0 { -hc } else { hc })
+    }
+}
+
+/// Decodes to the balanced representative in `(-modulus/2, modulus/2]`
+impl<T: BigIntTrait> Decode<T> for i64 {
+    fn decode(hc: &HenselCode<T>) -> Result<Self, CodingError> {
+        let (negative, abs) = hc.to_signed_bigint();
+        let abs = be_bytes_to_u128(&abs.to_be_bytes()).map_err(|_| CodingError::IntegerOverflow)?;
+        let n = i64::try_from(abs).map_err(|_| CodingError::IntegerOverflow)?;
+        Ok(if negative { -n } else { n })
+    }
+}
+
 /// Pretty-prints HenselCode
 impl<T: BigIntTrait> fmt::Display for HenselCode<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -127,6 +733,27 @@ impl<T: BigIntTrait> fmt::Display for HenselCode<T> {
     }
 }
 
+/// Debug-prints HenselCode with its modulus and residue
+impl<T: BigIntTrait> fmt::Debug for HenselCode<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("HenselCode")
+            .field("modulus", &self.modulus)
+            .field("residue", &self.res)
+            .finish()
+    }
+}
+
+/// Pretty-prints HenselCode in hexadecimal
+impl<T: BigIntTrait + fmt::LowerHex> fmt::LowerHex for HenselCode<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            write!(f, "{:#x} (mod {:#x})", self.res, self.modulus)
+        } else {
+            write!(f, "{:x} (mod {:x})", self.res, self.modulus)
+        }
+    }
+}
+
 /// Given a prime `p` and a rational `r = num/denom`, where p does not divide denom,
 /// returns `r (mod p)`
 impl<T: BigIntTrait> From<(&T, &Rational<T>)> for HenselCode<T> {
@@ -136,10 +763,12 @@ impl<T: BigIntTrait> From<(&T, &Rational<T>)> for HenselCode<T> {
         let denom = new_hensel_code(g, &r.denom);
         let num = new_hensel_code(g, &r.num);
 
-        if PartialEq::ne(&g.gcd(&r.denom), &T::one()) {
-            Self::generate_zero(g.clone())
-        } else {
-            num * (denom.invert())
+        // no branching on `r`, which is secret when decrypting
+        let (is_unit, inverse) = denom.extended_inverse();
+        let product = num.res.mul(&inverse).rem(g);
+        HenselCode {
+            modulus: g.clone(),
+            res: T::conditional_select(&T::zero(), &product, is_unit),
         }
     }
 }
@@ -159,7 +788,366 @@ mod tests {
 
         let hc1 = new_hensel_code(&p1, &n1);
 
-        assert_eq!(hc1.res.mul(&hc1.invert().res).rem(&p1), T::one());
+        assert_eq!(
+            BigIntTrait::mul(&hc1.res, &hc1.invert().res).rem(&p1),
+            T::one()
+        );
+    }
+
+    #[test]
+    fn invert_zero_divisor() {
+        let g = T::from_u128(15);
+        assert!(new_hensel_code(&g, &T::from_u128(6)).try_invert().is_none());
+        assert!(new_hensel_code(&g, &T::zero()).try_invert().is_none());
+        let inverse = new_hensel_code(&g, &T::from_u128(7)).try_invert().unwrap();
+        assert_eq!(inverse.res, T::from_u128(13));
+    }
+
+    #[test]
+    fn change_modulus() {
+        let (p1, p2) = (T::from_u128(4919), T::from_u128(7));
+        let g = p1.mul(&p2);
+        let hc = new_hensel_code(&g, &T::from_u128(30000));
+
+        let reduced = hc.change_modulus(&p2);
+        assert_eq!(reduced.modulus, p2);
+        assert_eq!(reduced.res, T::from_u128(30000 % 7));
+
+        let expanded = reduced.change_modulus(&g);
+        assert_eq!(expanded.modulus, g);
+        assert_eq!(expanded.res, reduced.res);
+    }
+
+    #[test]
+    fn new_hensel_code_checked() {
+        let g = T::from_u128(7919);
+        let hc = super::new_hensel_code_checked(&g, &T::from_u128(7918)).unwrap();
+        assert_eq!(hc.res, T::from_u128(7918));
+        for n in [7919, 30000] {
+            assert_eq!(
+                super::new_hensel_code_checked(&g, &T::from_u128(n)).err(),
+                Some(super::ResidueTooLarge)
+            );
+            // the lenient version reduces instead
+            assert_eq!(
+                new_hensel_code(&g, &T::from_u128(n)).res,
+                T::from_u128(n % 7919)
+            );
+        }
+    }
+
+    #[test]
+    fn try_from_parts() {
+        use num_bigint_dig::BigInt;
+
+        let g = T::from_u128(7919);
+        let hc = HenselCode::try_from_parts(&g, &T::from_u128(1337)).unwrap();
+        assert_eq!((hc.modulus, hc.res), (g.clone(), T::from_u128(1337)));
+        assert!(HenselCode::try_from_parts(&g, &T::zero()).is_ok());
+        for n in [7919, 7920, u128::MAX] {
+            assert_eq!(
+                HenselCode::try_from_parts(&g, &T::from_u128(n)).err(),
+                Some(super::ResidueTooLarge)
+            );
+        }
+        assert_eq!(
+            HenselCode::try_from_parts(&BigInt::from(7919), &BigInt::from(-1)).err(),
+            Some(super::ResidueTooLarge)
+        );
+    }
+
+    #[test]
+    fn new_hensel_codes() {
+        let ns: Vec<T> = [0, 1, 7918, 7919, 30000, u128::MAX]
+            .into_iter()
+            .map(T::from_u128)
+            .collect();
+        for g in [T::from_u128(7919), T::from_u128(7919 * 2)] {
+            let batch = super::new_hensel_codes(&g, &ns);
+            assert_eq!(batch.len(), ns.len());
+            for (hc, n) in batch.iter().zip(&ns) {
+                let expected = new_hensel_code(&g, n);
+                assert_eq!(hc.modulus, expected.modulus);
+                assert_eq!(hc.res, expected.res);
+            }
+        }
+    }
+
+    #[test]
+    fn try_into_u128() {
+        use crate::bigint::U128Overflow;
+        use std::convert::TryFrom;
+
+        let hc = new_hensel_code(&T::from_u128(7919), &T::from_u128(1337));
+        assert_eq!(u128::try_from(&hc), Ok(1337));
+        let g = T::from_u128(u128::MAX).mul(&T::from_u128(7919));
+        let hc = new_hensel_code(&g, &T::from_u128(u128::MAX).add(&T::one()));
+        assert_eq!(u128::try_from(&hc), Err(U128Overflow));
+    }
+
+    #[test]
+    fn map_residue() {
+        let g = T::from_u128(7919);
+        let double = |n: T| n.mul(&T::from_u128(2));
+        let hc = new_hensel_code(&g, &T::from_u128(1337));
+        assert_eq!(hc.map_residue(double).res, T::from_u128(2674));
+        let hc = new_hensel_code(&g, &T::from_u128(7000));
+        let doubled = hc.map_residue(double);
+        assert_eq!(doubled.modulus, g);
+        assert_eq!(doubled.res, T::from_u128(14000 - 7919));
+    }
+
+    #[test]
+    fn negate() {
+        let g = T::from_u128(7919);
+        let zero = HenselCode::generate_zero(g.clone());
+        assert_eq!((-&zero).res, T::zero());
+        for n in [1, 2, 1337, 7918] {
+            let a = new_hensel_code(&g, &T::from_u128(n));
+            let minus_a = -&a;
+            assert_eq!(minus_a.res, T::from_u128(7919 - n));
+            assert_eq!((a + minus_a).res, zero.res);
+        }
+    }
+
+    #[test]
+    fn to_signed_bigint() {
+        let g = T::from_u128(7919);
+        let signed = |n| new_hensel_code(&g, &T::from_u128(n)).to_signed_bigint();
+        assert_eq!(signed(0), (false, T::zero()));
+        assert_eq!(signed(42), (false, T::from_u128(42)));
+        // 7919/2 = 3959
+        assert_eq!(signed(3959), (false, T::from_u128(3959)));
+        assert_eq!(signed(3960), (true, T::from_u128(3959)));
+        assert_eq!(signed(7918), (true, T::one()));
+        let minus_five = -new_hensel_code(&g, &T::from_u128(5));
+        assert_eq!(minus_five.to_signed_bigint(), (true, T::from_u128(5)));
+    }
+
+    #[test]
+    fn is_unit() {
+        // 7919 * 37 is composite
+        let g = T::from_u128(7919 * 37);
+        let unit = new_hensel_code(&g, &T::from_u128(1337));
+        assert!(unit.is_unit());
+        assert!(unit.try_invert().is_some());
+
+        let zero_divisor = new_hensel_code(&g, &T::from_u128(37 * 12));
+        assert!(!zero_divisor.is_unit());
+        assert!(zero_divisor.try_invert().is_none());
+        assert!(!new_hensel_code(&g, &T::zero()).is_unit());
+    }
+
+    #[test]
+    fn reinterpret() {
+        use crate::rational::Rational;
+        let (p, q) = (T::from_u128(7919), T::from_u128(1009));
+        let hc = HenselCode::from((
+            &p,
+            &Rational {
+                num: T::from_u128(2),
+                denom: T::from_u128(3),
+            },
+        ));
+        assert_eq!(hc.res, T::from_u128(5280));
+
+        let under_q = hc.reinterpret(&q);
+        assert_eq!(under_q.modulus, q);
+        assert_eq!(under_q.res, T::from_u128(5280 % 1009));
+        assert_ne!(Rational::from(&under_q), Rational::from(&hc));
+        let back_under_p = under_q.reinterpret(&p);
+        assert_ne!(back_under_p.res, hc.res);
+    }
+
+    #[test]
+    fn display_hex() {
+        type U = num_bigint_dig::BigInt;
+        let hc = new_hensel_code(&U::from_u128(7919), &U::from_u128(255));
+        assert_eq!(format!("{}", hc), "255 (mod 7919)");
+        assert_eq!(format!("{:x}", hc), "ff (mod 1eef)");
+        assert_eq!(format!("{:#x}", hc), "0xff (mod 0x1eef)");
+    }
+
+    #[test]
+    fn padic_digits() {
+        let p = T::from_u128(7);
+        // 7^4 = 2401, 1000 = 2*7^3 + 6*7^2 + 2*7 + 6
+        let hc = new_hensel_code(&T::from_u128(2401), &T::from_u128(1000));
+        let digits: Vec<T> = [6, 2, 6, 2].into_iter().map(T::from_u128).collect();
+        assert_eq!(hc.digits(&p), digits);
+        assert_eq!(hc.digit_iter(&p).collect::<Vec<T>>(), hc.digits(&p));
+
+        // leading zero digits are kept up to the precision of the modulus
+        let hc = new_hensel_code(&T::from_u128(2401), &T::from_u128(8));
+        let digits: Vec<T> = [1, 1, 0, 0].into_iter().map(T::from_u128).collect();
+        assert_eq!(hc.digit_iter(&p).collect::<Vec<T>>(), digits);
+        assert_eq!(hc.digits(&p), digits);
+    }
+
+    #[test]
+    fn debug_hensel_code() {
+        let hc = new_hensel_code(&T::from_u128(7919), &T::from_u128(38));
+        assert_eq!(
+            format!("{:?}", hc),
+            "HenselCode { modulus: 7919, residue: 38 }"
+        );
+    }
+
+    #[test]
+    fn raw_residue_operations() {
+        let (p, n) = (T::from_u128(4919), T::from_u128(38));
+        let hc = new_hensel_code(&p, &n);
+        let residue = hc.to_residue().unwrap();
+
+        let squared = HenselCode::from_residue(residue.square());
+        assert_eq!(squared.modulus, p);
+        assert_eq!(squared.res, (&hc * &hc).res);
+
+        let even = new_hensel_code(&T::from_u128(16), &n);
+        assert!(even.to_residue().is_none());
+    }
+
+    #[test]
+    fn combine_with_precision() {
+        // 1/3 modulo 7^2 and 7^4
+        let (p2, p4) = (T::from_u128(49), T::from_u128(2401));
+        let low = new_hensel_code(&p2, &T::from_u128(33));
+        let high = new_hensel_code(&p4, &T::from_u128(1601));
+        for combined in [
+            low.combine_with_precision(&high),
+            high.combine_with_precision(&low),
+        ] {
+            let combined = combined.unwrap();
+            assert_eq!(combined.modulus, p4);
+            assert_eq!(combined.res, high.res);
+        }
+        assert_eq!(high.combine_with_precision(&high).unwrap().res, high.res);
+
+        let other = new_hensel_code(&p2, &T::from_u128(34));
+        assert_eq!(
+            other.combine_with_precision(&high).err(),
+            Some(super::Inconsistent)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot combine codes")]
+    fn combine_with_precision_unrelated_moduli() {
+        let a = new_hensel_code(&T::from_u128(49), &T::one());
+        let b = new_hensel_code(&T::from_u128(121), &T::one());
+        let _ = a.combine_with_precision(&b);
+    }
+
+    #[test]
+    fn chinese_remainder_shared_factor() {
+        let (g1, g2) = (T::from_u128(6), T::from_u128(9));
+        let agreeing = super::chinese_remainder_coprime_split(
+            new_hensel_code(&g1, &T::from_u128(4)),
+            new_hensel_code(&g2, &T::from_u128(1)),
+        )
+        .unwrap();
+        assert_eq!(agreeing.modulus, T::from_u128(18));
+        assert_eq!(agreeing.res, T::from_u128(10));
+
+        let disagreeing = super::chinese_remainder_coprime_split(
+            new_hensel_code(&g1, &T::from_u128(2)),
+            new_hensel_code(&g2, &T::from_u128(1)),
+        );
+        assert_eq!(disagreeing.err(), Some(super::Incompatible));
+
+        // coprime moduli behave as chinese_remainder
+        let (p1, p2) = (T::from_u128(4919), T::from_u128(7));
+        let (hc1, hc2) = (
+            new_hensel_code(&p1, &T::from_u128(38)),
+            new_hensel_code(&p2, &T::from_u128(2)),
+        );
+        let result = super::chinese_remainder_coprime_split(hc1.clone(), hc2.clone()).unwrap();
+        assert_eq!(result.res, super::chinese_remainder(hc1, hc2).res);
+    }
+
+    #[test]
+    fn checked_chinese_remainder() {
+        type T2 = crate::bigint::WrappingCryptoBigInt<2>;
+        // 2^89 - 1 and 2^31 - 1 are primes, their product needs 120 bits
+        let (g1, g2) = (T2::from_u128((1 << 89) - 1), T2::from_u128((1 << 31) - 1));
+        let (n1, n2) = (g1.sub(&T2::from_u128(5)), g2.sub(&T2::from_u128(7)));
+        let hc =
+            super::checked_chinese_remainder(new_hensel_code(&g1, &n1), new_hensel_code(&g2, &n2))
+                .unwrap();
+        assert_eq!(hc.modulus, BigIntTrait::mul(&g1, &g2));
+        assert_eq!(hc.res.rem(&g1), n1);
+        assert_eq!(hc.res.rem(&g2), n2);
+        // `chinese_remainder` wraps on `g2*i2*n1`, which needs about 209 bits
+        let wrapped =
+            super::chinese_remainder(new_hensel_code(&g1, &n1), new_hensel_code(&g2, &n2));
+        assert_ne!(wrapped.res, hc.res);
+
+        // 2^89 - 1 and 2^61 - 1: the product needs 150 bits
+        let g3 = T2::from_u128((1 << 61) - 1);
+        assert!(super::checked_chinese_remainder(
+            new_hensel_code(&g1, &n1),
+            new_hensel_code(&g3, &n2),
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn chinese_remainder_widen() {
+        type T2 = crate::bigint::WrappingCryptoBigInt<2>;
+        // 2^127 - 1 and 2^89 - 1 are primes, their product needs 216 bits
+        let (g1, g2) = (T2::from_u128(u128::MAX >> 1), T2::from_u128((1 << 89) - 1));
+        let (n1, n2) = (g1.sub(&T2::from_u128(5)), g2.sub(&T2::from_u128(7)));
+        let hc = super::chinese_remainder_widen::<2, 4>(
+            new_hensel_code(&g1, &n1),
+            new_hensel_code(&g2, &n2),
+        );
+        assert_eq!(hc.modulus, g1.resize::<4>().mul(&g2.resize::<4>()));
+        assert_eq!(hc.res.rem(&g1.resize::<4>()), n1.resize::<4>());
+        assert_eq!(hc.res.rem(&g2.resize::<4>()), n2.resize::<4>());
+        assert!(hc.res < hc.modulus);
+    }
+
+    #[test]
+    #[should_panic]
+    fn chinese_remainder_widen_too_narrow() {
+        type T2 = crate::bigint::WrappingCryptoBigInt<2>;
+        let hc = new_hensel_code(&T2::from_u128(7919), &T2::from_u128(1));
+        super::chinese_remainder_widen::<2, 3>(hc.clone(), hc);
+    }
+
+    #[test]
+    fn transport_bytes() {
+        let hc = new_hensel_code(&T::from_u128(7919 * 5897), &T::from_u128(1337));
+        let bytes = hc.to_transport_bytes();
+        assert_eq!(
+            bytes,
+            [0, 0, 0, 4, 0x02, 0xc8, 0x8f, 0x67, 0, 0, 0, 2, 0x05, 0x39]
+        );
+        let decoded = HenselCode::<T>::from_transport_bytes(&bytes).unwrap();
+        assert_eq!(decoded.modulus, hc.modulus);
+        assert_eq!(decoded.res, hc.res);
+
+        for length in 0..bytes.len() {
+            assert_eq!(
+                HenselCode::<T>::from_transport_bytes(&bytes[..length]).err(),
+                Some(super::TransportError::Truncated)
+            );
+        }
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert_eq!(
+            HenselCode::<T>::from_transport_bytes(&trailing).err(),
+            Some(super::TransportError::TrailingBytes)
+        );
+        assert_eq!(
+            HenselCode::<T>::from_transport_bytes(&[0, 0, 0, 0, 0, 0, 0, 0]).err(),
+            Some(super::TransportError::ZeroModulus)
+        );
+        // the residue 7919 is not reduced modulo 37
+        assert_eq!(
+            HenselCode::<T>::from_transport_bytes(&[0, 0, 0, 1, 37, 0, 0, 0, 2, 0x1e, 0xef]).err(),
+            Some(super::TransportError::ResidueOutOfRange)
+        );
     }
 
     #[test]
@@ -173,4 +1161,100 @@ mod tests {
         assert_eq!((result.res.rem(&T::from_u128(4919))), n1.res.clone());
         assert_eq!((result.res.rem(&T::from_u128(7))), n2.res.clone());
     }
+
+    #[test]
+    fn random() {
+        use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+
+        let modulus = T::from_u128(7919);
+        for _ in 0..100 {
+            let hc = HenselCode::random(&modulus);
+            assert_eq!(hc.modulus, modulus);
+            assert!(hc.res < modulus);
+        }
+        let (mut rng1, mut rng2) = (ChaCha20Rng::seed_from_u64(1), ChaCha20Rng::seed_from_u64(1));
+        let hc1 = HenselCode::random_with_rng(&modulus, &mut rng1);
+        let hc2 = HenselCode::random_with_rng(&modulus, &mut rng2);
+        assert!(hc1.res < modulus);
+        assert_eq!(hc1.res, hc2.res);
+    }
+
+    #[test]
+    fn pow_naf_matches_pow() {
+        type T4 = crate::bigint::WrappingCryptoBigInt<4>;
+        for modulus in [T4::from_u128(7919), T4::from_u128((1 << 89) - 1)] {
+            for _ in 0..10 {
+                // a non-zero residue, so that negative digits can be inverted
+                let res = T4::random_mod(&modulus.sub(&T4::one())).add(&T4::one());
+                let hc = new_hensel_code(&modulus, &res);
+                let exponent = T4::random_mod(&T4::from_u128(u128::MAX));
+                let expected = hc.pow_binary(&exponent);
+                for window in 2..=6 {
+                    assert_eq!(hc.pow_naf(&exponent, window).res, expected.res);
+                }
+            }
+        }
+        let hc = new_hensel_code(&T::from_u128(7919), &T::from_u128(3));
+        assert_eq!(hc.pow(&T::from_u128(5)).res, T::from_u128(243));
+        assert_eq!(hc.pow(&T::from_u128(7918)).res, T::one());
+        assert_eq!(hc.pow_naf(&T::zero(), 4).res, T::one());
+
+        // 37 is not invertible modulo 7919*37, and 7 = 8 - 1 has a negative digit
+        let hc = new_hensel_code(&T::from_u128(7919 * 37), &T::from_u128(37));
+        assert!(hc.try_invert().is_none());
+        assert_eq!(T::from_u128(7).to_naf(2), vec![-1, 0, 0, 1]);
+        let expected = hc.pow_binary(&T::from_u128(7));
+        assert_eq!(hc.pow_naf(&T::from_u128(7), 2).res, expected.res);
+        assert_eq!(hc.pow(&T::from_u128(7)).res, expected.res);
+        // long exponents go through the NAF
+        let exponent = T::from_u128(u128::MAX).mul(&T::from_u128(7919));
+        assert_eq!(hc.pow(&exponent).res, hc.pow_binary(&exponent).res);
+    }
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn arbitrary() {
+        use arbitrary::{Arbitrary, Unstructured};
+        use rand::RngCore;
+
+        type T4 = crate::bigint::WrappingCryptoBigInt<4>;
+        let mut bytes = [0; 4096];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        let mut u = Unstructured::new(&bytes);
+        for _ in 0..50 {
+            let hc = HenselCode::<T4>::arbitrary(&mut u).unwrap();
+            assert!(!hc.modulus.is_zero());
+            assert!(hc.res < hc.modulus);
+        }
+        let hc = HenselCode::<T4>::arbitrary(&mut Unstructured::new(&[])).unwrap();
+        assert_eq!((hc.modulus, hc.res), (T4::one(), T4::zero()));
+    }
+
+    #[test]
+    fn encode_decode_traits() {
+        use super::{CodingError, Decode, Encode};
+        use crate::rational::Rational;
+
+        let p = T::from_u128(7919);
+        for n in [0_i64, 1, 42, -1, -42, 3959, -3959] {
+            let hc = n.encode(&p).unwrap();
+            assert!(hc.res < p);
+            assert_eq!(i64::decode(&hc), Ok(n));
+        }
+        assert_eq!((-1_i64).encode(&p).unwrap().res, T::from_u128(7918));
+
+        let n = T::from_u128(7919 + 5);
+        assert_eq!(T::decode(&n.encode(&p).unwrap()), Ok(T::from_u128(5)));
+
+        let r = Rational::<T> {
+            num: T::from_u128(2),
+            denom: T::from_u128(3),
+        };
+        let hc = r.encode(&p).unwrap();
+        assert_eq!(hc.res, T::from_u128(5280));
+        assert_eq!(Rational::decode(&hc), Ok(r));
+
+        let big = new_hensel_code(&T::from_u128(1 << 80), &T::from_u128(1 << 70));
+        assert_eq!(i64::decode(&big), Err(CodingError::IntegerOverflow));
+    }
 }
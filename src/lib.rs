@@ -1,20 +1,27 @@
 extern crate lazy_static;
 pub mod bigint;
+pub mod cipher_vec;
+pub mod ciphertext;
+pub mod circuit;
 pub mod crypto_parameters;
+pub mod dyn_bigint;
 pub mod hensel_code;
 pub mod macros;
 pub mod rational;
 pub mod shared;
+pub mod slot_map;
 
-use std::{clone::Clone, fmt, ops};
+pub use hensel_code::{decode, encode};
+
+use std::{clone::Clone, cmp, convert, fmt, ops};
 
 #[cfg(test)]
 mod tests {
     use super::bigint::BigIntTrait;
-    use super::hensel_code::{new_hensel_code, HenselCode};
+    use super::hensel_code::{new_hensel_code, poly_eval, CodingError, HenselCode};
     use super::rational::Rational;
     use crate::crypto_parameters::{
-        EncryptionScheme, PrivateKeySchemeCryptographicParameters,
+        EncryptionScheme, PrivateKeySchemeCryptographicParameters, PublicKey,
         PublicKeySchemeCryptographicParameters,
     };
     use lazy_static::lazy_static;
@@ -108,6 +115,48 @@ mod tests {
         simple_tester(&r3, &p);
     }
 
+    #[test]
+    fn encodes_and_decodes() {
+        fn simple_tester(r: &Rational<T>, p: &T) {
+            let hc = super::encode(r, p).unwrap();
+            assert_eq!(&hc.modulus, p);
+            assert_eq!(&super::decode(&hc).unwrap(), r);
+        }
+
+        let p: T = T::from_u128(7919);
+
+        // positive integer
+        let r1 = Rational::<T> {
+            num: T::from_u128(6),
+            denom: T::one(),
+        };
+        simple_tester(&r1, &p);
+
+        // integer inverse
+        let r2 = Rational::<T> {
+            num: T::one(),
+            denom: T::from_u128(8),
+        };
+        simple_tester(&r2, &p);
+
+        // general rational
+        let r3 = Rational::<T> {
+            num: T::from_u128(3),
+            denom: T::from_u128(4),
+        };
+        simple_tester(&r3, &p);
+
+        // denominator divisible by p
+        let r4 = Rational::<T> {
+            num: T::one(),
+            denom: p.clone(),
+        };
+        assert_eq!(
+            super::encode(&r4, &p).err(),
+            Some(CodingError::NonInvertibleDenominator)
+        );
+    }
+
     #[test]
     fn encrypt_decrypt() {
         let (p1, p2, p3, p4, p5) = (
@@ -130,6 +179,77 @@ mod tests {
         assert_eq!(message, decrypted);
     }
 
+    #[test]
+    fn encrypt_poly_eval_decrypt() {
+        let (p1, p2, p3, p4, p5) = (
+            T::from_u128(7919),
+            T::from_u128(37),
+            T::from_u128(41),
+            T::from_u128(5897),
+            T::from_u128(7759),
+        );
+        let crypto_params: PrivateKeySchemeCryptographicParameters<T> =
+            PrivateKeySchemeCryptographicParameters::<T>::new(p1, p2, p3, p4, p5);
+        let x: Rational<T> = Rational {
+            num: T::from_u128(2),
+            denom: T::from_u128(3),
+        };
+        let coeffs: Vec<Rational<T>> = [1, 2, 3]
+            .into_iter()
+            .map(|c| Rational {
+                num: T::from_u128(c),
+                denom: T::one(),
+            })
+            .collect();
+
+        // 1 + 2*x + 3*x^2
+        let clear_result = coeffs
+            .iter()
+            .rev()
+            .fold(Rational::default(), |acc, c| &(&acc * &x) + c);
+        let encrypted_coeffs: Vec<HenselCode<T>> = coeffs
+            .into_iter()
+            .map(|c| crypto_params.encrypt(c))
+            .collect();
+        let encrypted_result = poly_eval(&encrypted_coeffs, &crypto_params.encrypt(x.clone()));
+        let decrypted = crypto_params.decrypt(encrypted_result);
+        assert_eq!(clear_result, decrypted);
+    }
+
+    #[test]
+    fn encrypt_scalar_ops_decrypt() {
+        let crypto_params =
+            PrivateKeySchemeCryptographicParameters::<T>::try_from("7919,37,41,5897,7759").unwrap();
+        let message: Rational<T> = Rational {
+            num: T::from_u128(2),
+            denom: T::from_u128(3),
+        };
+        let k: Rational<T> = Rational {
+            num: T::from_u128(5),
+            denom: T::one(),
+        };
+        let ciphertext = crypto_params.encrypt(message.clone());
+        let sum = crypto_params.decrypt(ciphertext.add_scalar(&k.num));
+        assert_eq!(sum, &message + &k);
+        let product = crypto_params.decrypt(ciphertext.mul_scalar(&k.num));
+        assert_eq!(product, &message * &k);
+    }
+
+    #[test]
+    fn public_encrypt_add_scalar_decrypt() {
+        let message: Rational<T> = Rational {
+            num: T::from_u128(7),
+            denom: T::from_u128(3),
+        };
+        let k: Rational<T> = Rational {
+            num: T::from_u128(4),
+            denom: T::one(),
+        };
+        let ciphertext = PUBLIC_PARAMS.encrypt(message.clone());
+        let decrypted = PUBLIC_PARAMS.decrypt(ciphertext.add_scalar(&k.num));
+        assert_eq!(message + k, decrypted);
+    }
+
     #[test]
     fn public_encrypt_decrypt() {
         let message: Rational<T> = Rational {
@@ -143,6 +263,25 @@ mod tests {
         assert_eq!(message, decrypted);
     }
 
+    #[test]
+    fn public_encrypt_without_primes() {
+        let message: Rational<T> = Rational {
+            num: T::from_u128(7),
+            denom: T::from_u128(3),
+        };
+        // only the public values are copied out of the parameters
+        let public = PUBLIC_PARAMS.public_key();
+        let public_key = PublicKey {
+            lambda: public.lambda,
+            g: public.g.clone(),
+            g_prime: public.g_prime.clone(),
+            e: public.e.clone(),
+        };
+        let ciphertext = public_key.public_encrypt(message.clone());
+        assert_eq!(ciphertext.modulus, public_key.g);
+        assert_eq!(PUBLIC_PARAMS.decrypt(ciphertext), message);
+    }
+
     #[test]
     fn public_encrypt_add_decrypt() {
         let message1: Rational<T> = Rational {
@@ -54,3 +54,38 @@ macro_rules! big_int_blanket_impl {
         )+
     };
 }
+
+#[macro_export]
+macro_rules! impl_big_int_trait_op {
+    ($type: ident, $(($trait: ident, $function: ident)),+ ) => {
+        $(
+            impl<const L: usize> std::ops::$trait<&$type<L>> for &$type<L> {
+                type Output = $type<L>;
+                fn $function(self, other: &$type<L>) -> $type<L> {
+                    BigIntTrait::$function(self, other)
+                }
+            }
+
+            impl<const L: usize> std::ops::$trait<$type<L>> for $type<L> {
+                type Output = $type<L>;
+                fn $function(self, other: $type<L>) -> $type<L> {
+                    BigIntTrait::$function(&self, &other)
+                }
+            }
+
+            impl<const L: usize> std::ops::$trait<&$type<L>> for $type<L> {
+                type Output = $type<L>;
+                fn $function(self, other: &$type<L>) -> $type<L> {
+                    BigIntTrait::$function(&self, other)
+                }
+            }
+
+            impl<const L: usize> std::ops::$trait<$type<L>> for &$type<L> {
+                type Output = $type<L>;
+                fn $function(self, other: $type<L>) -> $type<L> {
+                    BigIntTrait::$function(self, &other)
+                }
+            }
+        )+
+    };
+}
@@ -1,13 +1,14 @@
 use super::{
+    cmp::Ordering,
     fmt,
     hensel_code::HenselCode,
-    ops::{Add, Mul},
+    ops::{Add, AddAssign, Div, Mul, MulAssign},
     Clone,
 };
 
-use crate::bigint::BigIntTrait;
+use crate::bigint::{abs, be_bytes_to_u128, BigIntTrait};
 
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone)]
 pub struct Rational<T: BigIntTrait> {
     pub num: T,
     pub denom: T,
@@ -16,11 +17,427 @@ pub struct Rational<T: BigIntTrait> {
 /// Basic functionalities: simplify common factors, resize
 impl<T: BigIntTrait> Rational<T> {
     pub fn reduce(&self) -> Self {
-        let gcd = &self.num.gcd(&self.denom);
+        // the gcd of a negative numerator may be negative, which would flip the denominator
+        let gcd = &abs(&self.num).gcd(&self.denom);
         let num = self.num.div(gcd);
         let denom = self.denom.div(gcd);
         Rational::<T> { num, denom }
     }
+
+    /// Same as `reduce`, simplifying the common factors of `self` in place
+    pub fn reduce_in_place(&mut self) {
+        let gcd = abs(&self.num).gcd(&self.denom);
+        self.num = self.num.div(&gcd);
+        self.denom = self.denom.div(&gcd);
+    }
+
+    /// Multiplies two Rational, returning `None` if the reduced product overflows. Common factors
+    /// are cancelled crosswise before multiplying, so the intermediate products stay small.
+    pub fn checked_mul(&self, other: &Self) -> Option<Self> {
+        let gcd1 = abs(&self.num).gcd(&other.denom);
+        let gcd2 = abs(&other.num).gcd(&self.denom);
+        let num = self.num.div(&gcd1).checked_mul(&other.num.div(&gcd2))?;
+        let denom = self.denom.div(&gcd2).checked_mul(&other.denom.div(&gcd1))?;
+        Some(Rational::<T> { num, denom }.reduce())
+    }
+
+    /// Adds two Rational, returning `None` if the reduced sum overflows. The sum is computed over
+    /// the lcm of the denominators, so the intermediate products stay small.
+    pub fn checked_add(&self, other: &Self) -> Option<Self> {
+        let gcd1 = self.denom.gcd(&other.denom);
+        let num1 = self.num.checked_mul(&other.denom.div(&gcd1))?;
+        let num2 = other.num.checked_mul(&self.denom.div(&gcd1))?;
+        let num = num1.checked_add(&num2)?;
+        // the remaining common factors of the sum and the lcm all divide `gcd1`
+        let gcd2 = abs(&num).gcd(&gcd1);
+        let denom = self.denom.div(&gcd1).checked_mul(&other.denom.div(&gcd2))?;
+        Some(
+            Rational::<T> {
+                num: num.div(&gcd2),
+                denom,
+            }
+            .reduce(),
+        )
+    }
+
+    /// Rewrites `a` and `b` over their least common denominator, returning the two scaled
+    /// numerators and the shared denominator
+    pub fn common_denominator(a: &Self, b: &Self) -> (T, T, T) {
+        let gcd = a.denom.gcd(&b.denom);
+        let (a_factor, b_factor) = (b.denom.div(&gcd), a.denom.div(&gcd));
+        let denom = a.denom.mul(&a_factor);
+        (a.num.mul(&a_factor), b.num.mul(&b_factor), denom)
+    }
+
+    /// Raises the rational to the integer power `exponent`, inverting it for negative exponents.
+    /// Panics when raising zero to a negative power.
+    pub fn pow(&self, exponent: i32) -> Self {
+        let r = self.reduce();
+        let (num, denom) = if exponent < 0 {
+            if r.num.is_zero() {
+                panic!("cannot raise '{}' to the negative power {}", self, exponent);
+            }
+            (r.denom, r.num)
+        } else {
+            (r.num, r.denom)
+        };
+        let exponent = exponent.unsigned_abs() as u128;
+        Rational::<T> {
+            num: num.pow(exponent),
+            denom: denom.pow(exponent),
+        }
+        .reduce()
+    }
+
+    /// Converts the rational to a `f64`. When the numerator or the denominator has more than
+    /// `F64_BYTES` bytes, the same number of low bytes is dropped from both so that they convert
+    /// to finite floats.
+    pub fn to_f64(&self) -> f64 {
+        let (num_sign, num) = signed_bytes(&self.num);
+        let (denom_sign, denom) = signed_bytes(&self.denom);
+        let shift = num.len().max(denom.len()).saturating_sub(F64_BYTES);
+        num_sign * denom_sign * truncated_f64(&num, shift) / truncated_f64(&denom, shift)
+    }
+
+    /// Returns the reduced rational if the absolute value of its numerator is at most `num_bound`
+    /// and its denominator at most `denom_bound`, and `None` otherwise
+    pub fn clamp_to_bound(&self, num_bound: &T, denom_bound: &T) -> Option<Self> {
+        let r = self.reduce();
+        let abs_num = if r.num < T::zero() {
+            T::zero().sub(&r.num)
+        } else {
+            r.num.clone()
+        };
+        if abs_num <= *num_bound && r.denom <= *denom_bound {
+            Some(r)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the reduced reciprocal `denom/num`, keeping the denominator positive, or an error
+    /// if the numerator is zero
+    pub fn inv(&self) -> Result<Self, ZeroNumerator> {
+        if self.num.is_zero() {
+            return Err(ZeroNumerator);
+        }
+        let r = self.reduce();
+        if r.num < T::zero() {
+            Ok(Rational::<T> {
+                num: T::zero().sub(&r.denom),
+                denom: T::zero().sub(&r.num),
+            })
+        } else {
+            Ok(Rational::<T> {
+                num: r.denom,
+                denom: r.num,
+            })
+        }
+    }
+
+    /// Returns the rational closest to `self` whose denominator is at most `max_denom`, computed
+    /// from the convergents and semiconvergents of the continued fraction expansion of `self`.
+    /// Panics if `max_denom` is zero.
+    pub fn best_approximation(&self, max_denom: &T) -> Self {
+        if max_denom.is_zero() {
+            panic!("cannot approximate '{}' with a zero denominator", self);
+        }
+        if self.num < T::zero() {
+            let r = Rational::<T> {
+                num: T::zero().sub(&self.num),
+                denom: self.denom.clone(),
+            }
+            .best_approximation(max_denom);
+            return Rational::<T> {
+                num: T::zero().sub(&r.num),
+                denom: r.denom,
+            };
+        }
+        let r = self.reduce();
+        if r.denom <= *max_denom {
+            return r;
+        }
+        // p0/q0 and p1/q1 are the last two convergents
+        let (mut p0, mut q0, mut p1, mut q1) = (T::zero(), T::one(), T::one(), T::zero());
+        let (mut n, mut d) = (r.num.clone(), r.denom.clone());
+        loop {
+            let a = n.div(&d);
+            let q2 = q0.add(&a.mul(&q1));
+            if q2 > *max_denom {
+                break;
+            }
+            (p0, q0, p1, q1) = (p1.clone(), q1.clone(), p0.add(&a.mul(&p1)), q2);
+            (n, d) = (d.clone(), n.sub(&a.mul(&d)));
+        }
+        // the best semiconvergent below the bound, and the last convergent
+        let k = max_denom.sub(&q0).div(&q1);
+        let semiconvergent = Rational::<T> {
+            num: p0.add(&k.mul(&p1)),
+            denom: q0.add(&k.mul(&q1)),
+        };
+        let convergent = Rational::<T> { num: p1, denom: q1 };
+        // |x/y - num/denom| * y * denom, to compare the distances to `r`
+        let scaled_distance = |x: &Rational<T>| {
+            let (a, b) = (x.num.mul(&r.denom), r.num.mul(&x.denom));
+            if a > b {
+                a.sub(&b)
+            } else {
+                b.sub(&a)
+            }
+        };
+        if scaled_distance(&convergent).mul(&semiconvergent.denom)
+            <= scaled_distance(&semiconvergent).mul(&convergent.denom)
+        {
+            convergent
+        } else {
+            semiconvergent
+        }
+    }
+
+    /// Formats the rational followed by its reduced form when they differ, e.g. `6/8 (= 3/4)`
+    pub fn debug_string(&self) -> String {
+        let r = self.reduce();
+        if r.num == self.num && r.denom == self.denom {
+            self.to_string()
+        } else {
+            format!("{} (= {})", self, r)
+        }
+    }
+
+    /// Formats the reduced rational in decimal as `num/denom`, or `num` when it is an integer, so
+    /// that equal rationals always give the same string, whatever the big int backend
+    pub fn to_canonical_string(&self) -> String {
+        let r = self.canonical();
+        let num = if r.num < T::zero() {
+            format!("-{}", to_decimal(&T::zero().sub(&r.num)))
+        } else {
+            to_decimal(&r.num)
+        };
+        if r.denom == T::one() {
+            num
+        } else {
+            format!("{}/{}", num, to_decimal(&r.denom))
+        }
+    }
+
+    /// Reduces the rational after moving the sign to the numerator, as the gcd of signed big ints
+    /// expects a positive denominator
+    fn canonical(&self) -> Self {
+        if self.denom < T::zero() {
+            Rational {
+                num: T::zero().sub(&self.num),
+                denom: T::zero().sub(&self.denom),
+            }
+            .reduce()
+        } else {
+            self.reduce()
+        }
+    }
+
+    /// Parses a decimal `num/denom` or `num` as written by `to_canonical_string`, reducing the
+    /// result
+    pub fn from_canonical_str(s: &str) -> Result<Self, ParseRationalError> {
+        let parse = |n: &str| {
+            n.parse::<T>()
+                .map_err(|_| ParseRationalError::InvalidNumber(n.to_string()))
+        };
+        let r = match s.split_once('/') {
+            Some((num, denom)) => Rational {
+                num: parse(num)?,
+                denom: parse(denom)?,
+            },
+            None => Rational::from(parse(s)?),
+        };
+        if r.denom.is_zero() {
+            return Err(ParseRationalError::ZeroDenominator);
+        }
+        Ok(r.canonical())
+    }
+
+    /// Checks whether the rational is an integer, i.e. `denom` divides `num`
+    pub fn is_integer(&self) -> bool {
+        self.num.rem(&self.denom).is_zero()
+    }
+
+    /// Returns the integer value of the rational, or `None` if it is not an integer
+    pub fn to_bigint(&self) -> Option<T> {
+        if self.is_integer() {
+            Some(self.num.div(&self.denom))
+        } else {
+            None
+        }
+    }
+}
+
+/// Error returned when inverting a Rational whose numerator is zero
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct ZeroNumerator;
+
+impl fmt::Display for ZeroNumerator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "cannot invert a rational with a zero numerator")
+    }
+}
+
+impl std::error::Error for ZeroNumerator {}
+
+/// Error returned when parsing a Rational from a string fails
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum ParseRationalError {
+    InvalidNumber(String),
+    ZeroDenominator,
+}
+
+impl fmt::Display for ParseRationalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseRationalError::InvalidNumber(s) => write!(f, "cannot parse '{}' as a number", s),
+            ParseRationalError::ZeroDenominator => write!(f, "the denominator is zero"),
+        }
+    }
+}
+
+impl std::error::Error for ParseRationalError {}
+
+/// Formats the non-negative `n` in decimal, by chunks of 9 digits
+fn to_decimal<T: BigIntTrait>(n: &T) -> String {
+    let chunk_divisor = T::from_u128(1_000_000_000);
+    let mut chunks = Vec::new();
+    let mut n = n.clone();
+    loop {
+        let chunk = be_bytes_to_u128(&n.rem(&chunk_divisor).to_be_bytes()).unwrap();
+        chunks.push(chunk);
+        n = n.div(&chunk_divisor);
+        if n.is_zero() {
+            break;
+        }
+    }
+    let mut digits = chunks.pop().unwrap().to_string();
+    for chunk in chunks.iter().rev() {
+        digits.push_str(&format!("{:09}", chunk));
+    }
+    digits
+}
+
+/// Number of bytes of an integer which always converts to a finite `f64`
+const F64_BYTES: usize = 120;
+
+/// Returns the sign of `n` and the big-endian bytes of its absolute value
+fn signed_bytes<T: BigIntTrait>(n: &T) -> (f64, Vec<u8>) {
+    if *n < T::zero() {
+        (-1.0, T::zero().sub(n).to_be_bytes())
+    } else {
+        (1.0, n.to_be_bytes())
+    }
+}
+
+/// Converts the big-endian `bytes` to a `f64` after dropping their `shift` low bytes
+fn truncated_f64(bytes: &[u8], shift: usize) -> f64 {
+    bytes[..bytes.len().saturating_sub(shift)]
+        .iter()
+        .fold(0.0, |acc, byte| acc * 256.0 + *byte as f64)
+}
+
+/// Compares the fractions `n1/d1` and `n2/d2` with positive terms through their continued
+/// fraction expansions, so that no product can overflow
+fn cmp_positive<T: BigIntTrait>(mut n1: T, mut d1: T, mut n2: T, mut d2: T) -> Option<Ordering> {
+    loop {
+        let (q1, q2) = (n1.div(&d1), n2.div(&d2));
+        if q1 != q2 {
+            return q1.partial_cmp(&q2);
+        }
+        let (r1, r2) = (n1.rem(&d1), n2.rem(&d2));
+        match (r1.is_zero(), r2.is_zero()) {
+            (true, true) => return Some(Ordering::Equal),
+            (true, false) => return Some(Ordering::Less),
+            (false, true) => return Some(Ordering::Greater),
+            // r1/d1 < r2/d2 exactly when d2/r2 < d1/r1
+            (false, false) => (n1, d1, n2, d2) = (d2, r2, d1, r1),
+        }
+    }
+}
+
+/// Compares two Rational without computing their cross products
+fn cmp_reduced<T: BigIntTrait>(a: &Rational<T>, b: &Rational<T>) -> Option<Ordering> {
+    let (a, b) = (a.canonical(), b.canonical());
+    match (a.num < T::zero(), b.num < T::zero()) {
+        (true, false) => Some(Ordering::Less),
+        (false, true) => Some(Ordering::Greater),
+        (true, true) => cmp_positive(abs(&b.num), b.denom, abs(&a.num), a.denom),
+        (false, false) => cmp_positive(a.num, a.denom, b.num, b.denom),
+    }
+}
+
+/// Checks whether two Rational are equal as fractions, i.e. `num1*denom2 = num2*denom1`. When a
+/// cross product overflows, the reduced fractions are compared instead.
+impl<T: BigIntTrait> PartialEq for Rational<T> {
+    fn eq(&self, other: &Self) -> bool {
+        match (
+            self.num.checked_mul(&other.denom),
+            other.num.checked_mul(&self.denom),
+        ) {
+            (Some(a), Some(b)) => a == b,
+            _ => {
+                let (a, b) = (self.canonical(), other.canonical());
+                a.num == b.num && a.denom == b.denom
+            }
+        }
+    }
+}
+
+impl<T: BigIntTrait + Eq> Eq for Rational<T> {}
+
+/// Compares two Rational by comparing `num1*denom2` and `num2*denom1`. When a cross product
+/// overflows, the fractions are compared through their continued fraction expansions instead.
+impl<T: BigIntTrait> PartialOrd for Rational<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (
+            self.num.checked_mul(&other.denom),
+            other.num.checked_mul(&self.denom),
+        ) {
+            (Some(a), Some(b)) => a.partial_cmp(&b),
+            _ => cmp_reduced(self, other),
+        }
+    }
+}
+
+impl<T: BigIntTrait + Ord> Ord for Rational<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // any two values of a totally ordered `T` are comparable
+        self.partial_cmp(other).unwrap()
+    }
+}
+
+/// Converts an integer `n` into the Rational n/1
+impl<T: BigIntTrait> From<T> for Rational<T> {
+    fn from(n: T) -> Self {
+        Rational::<T> {
+            num: n,
+            denom: T::one(),
+        }
+    }
+}
+
+/// Defaults a Rational to 0/1
+impl<T: BigIntTrait> Default for Rational<T> {
+    fn default() -> Self {
+        Rational::<T> {
+            num: T::zero(),
+            denom: T::one(),
+        }
+    }
+}
+
+/// Builds a rational from an arbitrary numerator and denominator, replacing a zero denominator
+/// by one
+#[cfg(feature = "arbitrary")]
+impl<'a, T: BigIntTrait + arbitrary::Arbitrary<'a>> arbitrary::Arbitrary<'a> for Rational<T> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let num = T::arbitrary(u)?;
+        let denom = T::arbitrary(u)?;
+        let denom = if denom.is_zero() { T::one() } else { denom };
+        Ok(Rational::<T> { num, denom })
+    }
 }
 
 /// Pretty-prints Rational
@@ -30,7 +447,29 @@ impl<T: BigIntTrait> fmt::Display for Rational<T> {
     }
 }
 
+/// Debug-prints Rational with its numerator and denominator
+impl<T: BigIntTrait> fmt::Debug for Rational<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Rational")
+            .field("num", &self.num)
+            .field("denom", &self.denom)
+            .finish()
+    }
+}
+
+/// Pretty-prints Rational in hexadecimal
+impl<T: BigIntTrait + fmt::LowerHex> fmt::LowerHex for Rational<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            write!(f, "{:#x}/{:#x}", self.num, self.denom)
+        } else {
+            write!(f, "{:x}/{:x}", self.num, self.denom)
+        }
+    }
+}
+
 /// Adds two &Rational
+#[allow(clippy::needless_lifetimes)]
 impl<'a, 'b, T: BigIntTrait> Add<&'b Rational<T>> for &'a Rational<T> {
     type Output = Rational<T>;
     fn add(self, other: &'b Rational<T>) -> Rational<T> {
@@ -50,7 +489,24 @@ impl<T: BigIntTrait> Add<Rational<T>> for Rational<T> {
     }
 }
 
+/// Adds a Rational and a &Rational
+impl<T: BigIntTrait> Add<&Rational<T>> for Rational<T> {
+    type Output = Rational<T>;
+    fn add(self, other: &Rational<T>) -> Rational<T> {
+        &self + other
+    }
+}
+
+/// Adds a &Rational and a Rational
+impl<T: BigIntTrait> Add<Rational<T>> for &Rational<T> {
+    type Output = Rational<T>;
+    fn add(self, other: Rational<T>) -> Rational<T> {
+        self + &other
+    }
+}
+
 /// Multiplies two &Rational
+#[allow(clippy::needless_lifetimes)]
 impl<'a, 'b, T: BigIntTrait> Mul<&'b Rational<T>> for &'a Rational<T> {
     type Output = Rational<T>;
     fn mul(self, other: &'b Rational<T>) -> Rational<T> {
@@ -70,27 +526,123 @@ impl<T: BigIntTrait> Mul<Rational<T>> for Rational<T> {
     }
 }
 
-/// Given an element `hc` of Z/pZ, compute n_max = floor(sqrt((p-1)/2)), returns a rational
-/// num/denom where:
+/// Multiplies a Rational and a &Rational
+impl<T: BigIntTrait> Mul<&Rational<T>> for Rational<T> {
+    type Output = Rational<T>;
+    fn mul(self, other: &Rational<T>) -> Rational<T> {
+        &self * other
+    }
+}
+
+/// Multiplies a &Rational and a Rational
+impl<T: BigIntTrait> Mul<Rational<T>> for &Rational<T> {
+    type Output = Rational<T>;
+    fn mul(self, other: Rational<T>) -> Rational<T> {
+        self * &other
+    }
+}
+
+/// Multiplies a &Rational by an integer, cancelling the common factors of the integer and the
+/// denominator first
+impl<T: BigIntTrait> Mul<&T> for &Rational<T> {
+    type Output = Rational<T>;
+    fn mul(self, k: &T) -> Rational<T> {
+        let gcd = abs(k).gcd(&self.denom);
+        Rational::<T> {
+            num: self.num.mul(&k.div(&gcd)),
+            denom: self.denom.div(&gcd),
+        }
+        .reduce()
+    }
+}
+
+/// Multiplies a Rational by an integer
+impl<T: BigIntTrait> Mul<T> for Rational<T> {
+    type Output = Rational<T>;
+    fn mul(self, k: T) -> Rational<T> {
+        &self * &k
+    }
+}
+
+/// Divides a &Rational by a non-zero integer, cancelling the common factors of the integer and
+/// the numerator first
+impl<T: BigIntTrait> Div<&T> for &Rational<T> {
+    type Output = Rational<T>;
+    fn div(self, k: &T) -> Rational<T> {
+        if k.is_zero() {
+            panic!("cannot divide '{}' by zero", self)
+        }
+        // keep the denominator positive
+        let (num, k) = if *k < T::zero() {
+            (T::zero().sub(&self.num), T::zero().sub(k))
+        } else {
+            (self.num.clone(), k.clone())
+        };
+        let gcd = abs(&num).gcd(&k);
+        Rational::<T> {
+            num: num.div(&gcd),
+            denom: self.denom.mul(&k.div(&gcd)),
+        }
+        .reduce()
+    }
+}
+
+/// Divides a Rational by a non-zero integer
+impl<T: BigIntTrait> Div<T> for Rational<T> {
+    type Output = Rational<T>;
+    fn div(self, k: T) -> Rational<T> {
+        &self / &k
+    }
+}
+
+/// Adds a &Rational to a Rational, keeping the sum reduced
+impl<T: BigIntTrait> AddAssign<&Rational<T>> for Rational<T> {
+    fn add_assign(&mut self, other: &Rational<T>) {
+        self.num = self.num.mul(&other.denom).add(&self.denom.mul(&other.num));
+        self.denom = self.denom.mul(&other.denom);
+        self.reduce_in_place();
+    }
+}
+
+/// Adds a Rational to a Rational, keeping the sum reduced
+impl<T: BigIntTrait> AddAssign<Rational<T>> for Rational<T> {
+    fn add_assign(&mut self, other: Rational<T>) {
+        *self += &other;
+    }
+}
+
+/// Multiplies a Rational by a &Rational, keeping the product reduced
+impl<T: BigIntTrait> MulAssign<&Rational<T>> for Rational<T> {
+    fn mul_assign(&mut self, other: &Rational<T>) {
+        self.num = self.num.mul(&other.num);
+        self.denom = self.denom.mul(&other.denom);
+        self.reduce_in_place();
+    }
+}
+
+/// Multiplies a Rational by a Rational, keeping the product reduced
+impl<T: BigIntTrait> MulAssign<Rational<T>> for Rational<T> {
+    fn mul_assign(&mut self, other: Rational<T>) {
+        *self *= &other;
+    }
+}
+
+/// Given an element `hc` of Z/pZ, compute the Farey bound n_max = floor(sqrt(p/2)), returns a
+/// rational num/denom where:
 ///  i)   0 <= num   <= n_max,
 ///  ii)  0 <= denom <= 2*n_max,
 ///  iii) hc = num/denom (mod p)
 impl<T: BigIntTrait> From<&HenselCode<T>> for Rational<T> {
     fn from(hc: &HenselCode<T>) -> Self {
-        let n_max = hc.modulus.sub(&T::one()).div(&T::from_u128(2)).sqrt();
+        let n_max = T::farey_bound(&hc.modulus);
 
         let g = hc.modulus.clone();
         let (mut x0, mut x1) = (hc.modulus.clone(), hc.res.clone());
+        // a zero residue skips the loop, its result 0/1 is selected at the end without branching
+        let res_is_zero = x1.is_zero();
 
-        if x1.is_zero() {
-            return Rational::<T> {
-                num: T::zero(),
-                denom: T::one(),
-            };
-        }
         // perform (modified) extended euclidean algorithm on (g, n % g)
         let (mut y0, mut y1) = (T::zero(), T::one());
-        println!("n_max: {n_max}, x0: {x0}, x1: {x1}, y0: {y0}, y1: {y1}");
         while (x0 > n_max) && !x1.is_zero() {
             let q = x0.div(&x1);
             (x0, x1) = (x1.clone(), x0.sub(&q.mul(&x1)));
@@ -100,13 +652,11 @@ impl<T: BigIntTrait> From<&HenselCode<T>> for Rational<T> {
             //     ((y0 + g) - (q*y1 % g)) % g,
             // )
             (y0, y1) = (y1.clone(), (y0.add(&g).sub(&q.mul(&y1).rem(&g))).rem(&g));
-            println!("n_max: {n_max}, x0: {x0}, x1: {x1}, y0: {y0}, y1: {y1}");
         }
 
-        println!("result: {x0} / {y0}\n");
         Rational::<T> {
-            num: x0,
-            denom: y0.rem(&hc.modulus),
+            num: T::conditional_select(&x0, &T::zero(), res_is_zero),
+            denom: T::conditional_select(&y0.rem(&hc.modulus), &T::one(), res_is_zero),
         }
     }
 }
@@ -120,6 +670,298 @@ mod tests {
     // const L: usize = DEFAULT_LIMBS;
     type T = WrappingCryptoBigInt;
 
+    #[test]
+    fn reference_ops_match_owned() {
+        let from_u128 = <T as BigIntTrait>::from_u128;
+        let (a, b) = (
+            Rational::<T> {
+                num: from_u128(3),
+                denom: from_u128(4),
+            },
+            Rational::<T> {
+                num: from_u128(10),
+                denom: from_u128(9),
+            },
+        );
+        let (sum, product) = (a.clone() + b.clone(), a.clone() * b.clone());
+        for r in [&a + &b, a.clone() + &b, &a + b.clone()] {
+            assert_eq!((&r.num, &r.denom), (&sum.num, &sum.denom));
+        }
+        for r in [&a * &b, a.clone() * &b, &a * b.clone()] {
+            assert_eq!((&r.num, &r.denom), (&product.num, &product.denom));
+        }
+        assert_eq!((sum.num, sum.denom), (from_u128(67), from_u128(36)));
+        assert_eq!((product.num, product.denom), (from_u128(5), from_u128(6)));
+    }
+
+    #[test]
+    fn assign_ops_stay_reduced() {
+        let from_u128 = <T as BigIntTrait>::from_u128;
+        let mut sum = Rational::<T>::default();
+        let mut product = Rational::<T> {
+            num: from_u128(1),
+            denom: from_u128(1),
+        };
+        // sum_{k=1}^{20} 1/(k(k+1)) = 20/21 and prod_{k=1}^{20} k/(k+1) = 1/21
+        for k in 1..=20 {
+            sum += Rational::<T> {
+                num: from_u128(1),
+                denom: from_u128(k * (k + 1)),
+            };
+            product *= &Rational::<T> {
+                num: from_u128(k),
+                denom: from_u128(k + 1),
+            };
+            assert_eq!(sum.num.gcd(&sum.denom), T::one());
+            assert_eq!(product.num.gcd(&product.denom), T::one());
+        }
+        assert_eq!((sum.num, sum.denom), (from_u128(20), from_u128(21)));
+        assert_eq!((product.num, product.denom), (from_u128(1), from_u128(21)));
+    }
+
+    #[test]
+    fn inverse() {
+        let from_u128 = <T as BigIntTrait>::from_u128;
+        let r = Rational::<T> {
+            num: from_u128(6),
+            denom: from_u128(8),
+        };
+        let inverse = r.inv().unwrap();
+        assert_eq!((inverse.num, inverse.denom), (from_u128(4), from_u128(3)));
+        assert_eq!(Rational::<T>::default().inv(), Err(super::ZeroNumerator));
+
+        type U = num_bigint_dig::BigInt;
+        let negative = Rational::<U> {
+            num: U::from(-3),
+            denom: U::from(4),
+        };
+        let inverse = negative.inv().unwrap();
+        assert_eq!((inverse.num, inverse.denom), (U::from(-4), U::from(3)));
+    }
+
+    #[test]
+    fn from_integer() {
+        use crate::hensel_code::HenselCode;
+
+        let from_u128 = <T as BigIntTrait>::from_u128;
+        let r = Rational::from(from_u128(42));
+        assert_eq!((&r.num, &r.denom), (&from_u128(42), &T::one()));
+        let hc = HenselCode::from((&from_u128(7919), &r));
+        assert_eq!(hc.res, from_u128(42));
+        let decoded = Rational::from(&hc);
+        assert_eq!(decoded.to_bigint(), Some(from_u128(42)));
+    }
+
+    #[test]
+    fn clamp_to_bound() {
+        let from_u128 = <T as BigIntTrait>::from_u128;
+        let (num_bound, denom_bound) = (from_u128(62), from_u128(54));
+        let r = |num, denom| Rational::<T> {
+            num: from_u128(num),
+            denom: from_u128(denom),
+        };
+        let at_bound = r(62, 54).clamp_to_bound(&num_bound, &denom_bound).unwrap();
+        assert_eq!(
+            (at_bound.num, at_bound.denom),
+            (from_u128(31), from_u128(27))
+        );
+        assert!(r(31, 54).clamp_to_bound(&num_bound, &denom_bound).is_some());
+        // 126/110 reduces to 63/55
+        assert!(r(126, 110)
+            .clamp_to_bound(&num_bound, &denom_bound)
+            .is_none());
+        assert!(r(63, 1).clamp_to_bound(&num_bound, &denom_bound).is_none());
+        assert!(r(1, 55).clamp_to_bound(&num_bound, &denom_bound).is_none());
+
+        type U = num_bigint_dig::BigInt;
+        let negative = Rational::<U> {
+            num: U::from(-62),
+            denom: U::from(1),
+        };
+        assert!(negative.clamp_to_bound(&U::from(62), &U::from(1)).is_some());
+        assert!(negative.clamp_to_bound(&U::from(61), &U::from(1)).is_none());
+    }
+
+    #[test]
+    fn to_f64() {
+        type U = num_bigint_dig::BigInt;
+        let from_u128 = <T as BigIntTrait>::from_u128;
+        let r = Rational::<T> {
+            num: from_u128(3),
+            denom: from_u128(4),
+        };
+        assert_eq!(r.to_f64(), 0.75);
+        let negative = Rational::<U> {
+            num: U::from(-1),
+            denom: U::from(8),
+        };
+        assert_eq!(negative.to_f64(), -0.125);
+
+        // (2*10^400) / (3*10^400 + 1) ~ 2/3
+        let huge = U::from(10).pow(400);
+        let r = Rational::<U> {
+            num: &huge * U::from(2),
+            denom: &huge * U::from(3) + U::from(1),
+        };
+        assert!((r.to_f64() - 2.0 / 3.0).abs() < 1e-15);
+    }
+
+    #[test]
+    fn reduce_in_place() {
+        let from_u128 = <T as BigIntTrait>::from_u128;
+        let mut r = Rational::<T> {
+            num: from_u128(84),
+            denom: from_u128(36),
+        };
+        r.reduce_in_place();
+        assert_eq!((r.num, r.denom), (from_u128(7), from_u128(3)));
+    }
+
+    #[test]
+    fn default_rational() {
+        let r = Rational::<T>::default();
+        assert_eq!(r.num, T::zero());
+        assert_eq!(r.denom, T::one());
+    }
+
+    #[test]
+    fn rational_to_bigint() {
+        let r1 = Rational::<T> {
+            num: T::from_u128(6),
+            denom: T::from_u128(3),
+        };
+        assert!(r1.is_integer());
+        assert_eq!(r1.to_bigint(), Some(T::from_u128(2)));
+
+        let r2 = Rational::<T> {
+            num: T::from_u128(6),
+            denom: T::from_u128(8),
+        };
+        assert!(!r2.is_integer());
+        assert_eq!(r2.to_bigint(), None);
+    }
+
+    #[test]
+    fn display_hex() {
+        type U = num_bigint_dig::BigInt;
+        let r = Rational::<U> {
+            num: U::from_u128(255),
+            denom: U::from_u128(4096),
+        };
+        assert_eq!(format!("{}", r), "255/4096");
+        assert_eq!(format!("{:x}", r), "ff/1000");
+        assert_eq!(format!("{:#x}", r), "0xff/0x1000");
+    }
+
+    #[test]
+    fn debug_rational() {
+        let r = Rational::<T> {
+            num: T::from_u128(3),
+            denom: T::from_u128(4),
+        };
+        assert_eq!(format!("{:?}", r), "Rational { num: 3, denom: 4 }");
+    }
+
+    #[test]
+    fn orders_rationals() {
+        let from_u128 = <T as BigIntTrait>::from_u128;
+        let rational = |num, denom| Rational::<T> {
+            num: from_u128(num),
+            denom: from_u128(denom),
+        };
+        let mut rationals = vec![rational(2, 3), rational(1, 3), rational(1, 2)];
+        rationals.sort();
+        assert_eq!(
+            rationals,
+            vec![rational(1, 3), rational(1, 2), rational(2, 3)]
+        );
+        assert_eq!(rationals.iter().max(), Some(&rational(4, 6)));
+        assert_eq!(rational(2, 4), rational(1, 2));
+        assert!(rational(1, 3) < rational(1, 2));
+    }
+
+    #[test]
+    fn orders_rationals_with_overflowing_cross_products() {
+        type T2 = WrappingCryptoBigInt<2>;
+        let from_u128 = <T2 as BigIntTrait>::from_u128;
+        let rational = |num, denom| Rational::<T2> {
+            num: from_u128(num),
+            denom: from_u128(denom),
+        };
+        let k = u128::MAX / 2;
+        assert_eq!(rational(2 * k, 2 * (k - 1)), rational(k, k - 1));
+        assert_ne!(rational(2 * k, 2 * (k - 1)), rational(k, k - 2));
+        // n/(n-1) decreases towards 1
+        assert!(rational(u128::MAX, u128::MAX - 1) < rational(u128::MAX - 1, u128::MAX - 2));
+        assert!(rational(k, k - 2) > rational(2 * k, 2 * (k - 1)));
+        assert_eq!(
+            rational(k - 1, k).cmp(&rational(u128::MAX - 1, u128::MAX)),
+            std::cmp::Ordering::Less
+        );
+        assert_eq!(
+            rational(k, 3).cmp(&rational(k, 3)),
+            std::cmp::Ordering::Equal
+        );
+    }
+
+    #[test]
+    fn checked_ops_stay_reduced() {
+        type T2 = WrappingCryptoBigInt<2>;
+        let from_u128 = <T2 as BigIntTrait>::from_u128;
+        let rational = |num, denom| Rational::<T2> {
+            num: from_u128(num),
+            denom: from_u128(denom),
+        };
+
+        // 1/2 * 2/3 * ... * 99/100 = 1/100, while the unreduced product would overflow
+        let product = (1..100).fold(rational(1, 1), |acc, k| {
+            acc.checked_mul(&rational(k, k + 1)).unwrap()
+        });
+        assert_eq!(product.num, from_u128(1));
+        assert_eq!(product.denom, from_u128(100));
+
+        let big = rational(1 << 100, 3);
+        assert!(big.checked_mul(&big).is_none());
+        assert_eq!(
+            rational(1, 6).checked_add(&rational(1, 3)),
+            Some(rational(1, 2))
+        );
+        // the unreduced sum 4/(3*2^200) would overflow, while the reduced sum fits
+        assert_eq!(
+            rational(1, 1 << 100).checked_add(&rational(1, 3 << 100)),
+            Some(rational(1, 3 << 98))
+        );
+        assert_eq!(
+            rational(1, 1 << 100).checked_add(&rational(1, 1 << 100)),
+            Some(rational(1, 1 << 99))
+        );
+        assert!(rational(1, (1 << 127) - 1)
+            .checked_add(&rational(1, (1 << 127) - 3))
+            .is_none());
+    }
+
+    #[test]
+    fn rational_pow() {
+        let from_u128 = <T as BigIntTrait>::from_u128;
+        let rational = |num, denom| Rational::<T> {
+            num: from_u128(num),
+            denom: from_u128(denom),
+        };
+        let r = rational(2, 3);
+        assert_eq!(r.pow(3), rational(8, 27));
+        assert_eq!(r.pow(-1), rational(3, 2));
+        assert_eq!(r.pow(-2), rational(9, 4));
+        assert_eq!(r.pow(0), rational(1, 1));
+        assert_eq!(rational(0, 1).pow(2), rational(0, 1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_to_negative_power() {
+        let zero = Rational::<T>::default();
+        let _ = zero.pow(-1);
+    }
+
     #[test]
     fn adds_rationals() {
         fn simple_tester(r1: &Rational<T>, r2: &Rational<T>) {
@@ -152,4 +994,258 @@ mod tests {
         };
         simple_tester(&r12, &r22);
     }
+
+    #[test]
+    fn common_denominator() {
+        let from_u128 = <T as BigIntTrait>::from_u128;
+        let half = Rational::<T> {
+            num: from_u128(1),
+            denom: from_u128(2),
+        };
+        let third = Rational::<T> {
+            num: from_u128(1),
+            denom: from_u128(3),
+        };
+        assert_eq!(
+            Rational::common_denominator(&half, &third),
+            (from_u128(3), from_u128(2), from_u128(6))
+        );
+        let sixth = Rational::<T> {
+            num: from_u128(5),
+            denom: from_u128(6),
+        };
+        let tenth = Rational::<T> {
+            num: from_u128(3),
+            denom: from_u128(10),
+        };
+        assert_eq!(
+            Rational::common_denominator(&sixth, &tenth),
+            (from_u128(25), from_u128(9), from_u128(30))
+        );
+    }
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn arbitrary() {
+        use arbitrary::{Arbitrary, Unstructured};
+        use rand::RngCore;
+
+        type T4 = WrappingCryptoBigInt<4>;
+        let mut bytes = [0; 4096];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        let mut u = Unstructured::new(&bytes);
+        for _ in 0..50 {
+            let r = Rational::<T4>::arbitrary(&mut u).unwrap();
+            assert!(!r.denom.is_zero());
+        }
+        // exhausted input yields zeros, and the denominator falls back to one
+        let r = Rational::<T4>::arbitrary(&mut Unstructured::new(&[])).unwrap();
+        assert_eq!((r.num, r.denom), (T4::zero(), T4::one()));
+    }
+
+    #[test]
+    fn best_approximation() {
+        use num_bigint_dig::BigInt;
+
+        fn simple_tester<U: BigIntTrait>(num: u128, denom: u128, max_denom: u128) -> (U, U) {
+            let r = Rational::<U> {
+                num: U::from_u128(num),
+                denom: U::from_u128(denom),
+            }
+            .best_approximation(&U::from_u128(max_denom));
+            (r.num, r.denom)
+        }
+
+        let n = <T as BigIntTrait>::from_u128;
+        assert_eq!(simple_tester::<T>(355, 113, 100), (n(311), n(99)));
+        assert_eq!(simple_tester::<T>(355, 113, 10), (n(22), n(7)));
+        assert_eq!(simple_tester::<T>(355, 113, 113), (n(355), n(113)));
+        assert_eq!(simple_tester::<T>(314159, 100000, 1000), (n(355), n(113)));
+        assert_eq!(simple_tester::<T>(1, 3, 2), (n(1), n(2)));
+        assert_eq!(simple_tester::<T>(710, 226, 1000), (n(355), n(113)));
+        assert_eq!(simple_tester::<T>(7, 5, 1), (n(1), n(1)));
+
+        let r = Rational::<BigInt> {
+            num: BigInt::from(-314159),
+            denom: BigInt::from(100000),
+        }
+        .best_approximation(&BigInt::from(1000));
+        assert_eq!((r.num, r.denom), (BigInt::from(-355), BigInt::from(113)));
+    }
+
+    #[test]
+    fn debug_string() {
+        use num_bigint_dig::BigInt;
+
+        let rational = |num: i32, denom: i32| Rational::<BigInt> {
+            num: BigInt::from(num),
+            denom: BigInt::from(denom),
+        };
+        assert_eq!(rational(6, 8).debug_string(), "6/8 (= 3/4)");
+        assert_eq!(rational(-6, 8).debug_string(), "-6/8 (= -3/4)");
+        assert_eq!(rational(3, 4).debug_string(), "3/4");
+        let r = Rational::<T> {
+            num: T::from_u128(10),
+            denom: T::from_u128(4),
+        };
+        assert_eq!(r.debug_string(), format!("{} (= {})", r, r.reduce()));
+    }
+
+    #[test]
+    fn canonical_string() {
+        use super::ParseRationalError;
+        use num_bigint_dig::BigInt;
+
+        let r = |num: u128, denom: u128| Rational::<T> {
+            num: T::from_u128(num),
+            denom: T::from_u128(denom),
+        };
+        assert_eq!(r(6, 8).to_canonical_string(), "3/4");
+        assert_eq!(r(6, 8).to_canonical_string(), r(3, 4).to_canonical_string());
+        assert_eq!(r(8, 2).to_canonical_string(), "4");
+        assert_eq!(r(0, 5).to_canonical_string(), "0");
+        let large = r(u128::MAX, 1_000_000_007);
+        assert_eq!(
+            large.to_canonical_string(),
+            format!("{}/1000000007", u128::MAX)
+        );
+        for s in ["3/4", "4", "0", &large.to_canonical_string()] {
+            assert_eq!(
+                Rational::<T>::from_canonical_str(s)
+                    .unwrap()
+                    .to_canonical_string(),
+                s
+            );
+        }
+        assert_eq!(Rational::<T>::from_canonical_str("6/8").unwrap(), r(3, 4));
+        assert_eq!(
+            Rational::<T>::from_canonical_str("3/0").err(),
+            Some(ParseRationalError::ZeroDenominator)
+        );
+        assert_eq!(
+            Rational::<T>::from_canonical_str("3/x").err(),
+            Some(ParseRationalError::InvalidNumber("x".to_string()))
+        );
+
+        let signed = |num: i32, denom: i32| Rational::<BigInt> {
+            num: BigInt::from(num),
+            denom: BigInt::from(denom),
+        };
+        assert_eq!(signed(6, -8).to_canonical_string(), "-3/4");
+        assert_eq!(signed(-6, -8).to_canonical_string(), "3/4");
+        assert_eq!(
+            Rational::<BigInt>::from_canonical_str("-6/8").unwrap(),
+            signed(-3, 4)
+        );
+        assert_eq!(
+            Rational::<BigInt>::from_canonical_str("-6/-8").unwrap(),
+            signed(3, 4)
+        );
+    }
+
+    #[test]
+    fn scale_by_integer() {
+        use num_bigint_dig::BigInt;
+
+        let r = |num: u128, denom: u128| Rational::<T> {
+            num: T::from_u128(num),
+            denom: T::from_u128(denom),
+        };
+        let k = T::from_u128;
+        assert_eq!(&r(3, 4) * &k(2), r(3, 2));
+        assert_eq!(r(3, 4) * k(8), r(6, 1));
+        assert_eq!(r(3, 4) * k(0), r(0, 1));
+        assert_eq!(&r(3, 4) / &k(6), r(1, 8));
+        assert_eq!(r(6, 7) / k(4), r(3, 14));
+        // no overflow when the integer cancels with the denominator
+        let large = Rational::<T> {
+            num: T::one(),
+            denom: T::from_u128(1 << 100).mul(&T::from_u128(1 << 100)),
+        };
+        assert_eq!(large.clone() * T::from_u128(1 << 100), r(1, 1 << 100));
+        assert_eq!(
+            (large / T::from_u128(3)).denom,
+            T::from_u128(3 << 100).mul(&T::from_u128(1 << 100))
+        );
+
+        let signed = |num: i32, denom: i32| Rational::<BigInt> {
+            num: BigInt::from(num),
+            denom: BigInt::from(denom),
+        };
+        assert_eq!(signed(-3, 4) / BigInt::from(-6), signed(1, 8));
+        let divided = signed(3, 4) / BigInt::from(-6);
+        assert_eq!(
+            (divided.num, divided.denom),
+            (BigInt::from(-1), BigInt::from(8))
+        );
+        let scaled = signed(3, 4) * BigInt::from(-2);
+        assert_eq!(
+            (scaled.num, scaled.denom),
+            (BigInt::from(-3), BigInt::from(2))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot divide '3/4' by zero")]
+    fn divide_by_zero() {
+        use num_bigint_dig::BigInt;
+
+        let r = Rational::<BigInt> {
+            num: BigInt::from(3),
+            denom: BigInt::from(4),
+        };
+        let _ = r / BigInt::from(0);
+    }
+
+    #[test]
+    fn reduce_negative_numerator() {
+        use num_bigint_dig::BigInt;
+
+        // gcd(-1, 8) and gcd(-3, 6) are negative with num_bigint
+        for (num, denom, reduced) in [(-1, 8, (-1, 8)), (-3, 6, (-1, 2)), (-6, 8, (-3, 4))] {
+            let r = Rational::<BigInt> {
+                num: BigInt::from(num),
+                denom: BigInt::from(denom),
+            };
+            let expected = (BigInt::from(reduced.0), BigInt::from(reduced.1));
+            let r_reduced = r.reduce();
+            assert_eq!((r_reduced.num, r_reduced.denom), expected);
+            let mut r = r;
+            r.reduce_in_place();
+            assert_eq!((r.num, r.denom), expected);
+        }
+    }
+
+    #[test]
+    fn checked_ops_negative_numerator() {
+        use num_bigint_dig::BigInt;
+
+        let r = |num: i64, denom: i64| Rational::<BigInt> {
+            num: BigInt::from(num),
+            denom: BigInt::from(denom),
+        };
+        let parts = |r: Rational<BigInt>| (r.num, r.denom);
+        // the crosswise gcds of negative numerators are negative with num_bigint
+        assert_eq!(
+            parts(r(-1, 2).checked_mul(&r(1, 3)).unwrap()),
+            parts(r(-1, 6))
+        );
+        assert_eq!(
+            parts(r(1, 3).checked_mul(&r(-1, 2)).unwrap()),
+            parts(r(-1, 6))
+        );
+        assert_eq!(
+            parts(r(-3, 4).checked_mul(&r(2, 9)).unwrap()),
+            parts(r(-1, 6))
+        );
+        assert_eq!(
+            parts(r(-1, 2).checked_add(&r(0, 2)).unwrap()),
+            parts(r(-1, 2))
+        );
+        assert_eq!(
+            parts(r(-5, 6).checked_add(&r(1, 3)).unwrap()),
+            parts(r(-1, 2))
+        );
+        assert!(r(-1, 2).checked_mul(&r(1, 3)).unwrap() < r(0, 1));
+    }
 }
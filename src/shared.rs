@@ -1,4 +1,45 @@
 //use crypto_bigint::U256;
+use crypto_bigint::Limb;
 
 /// Default size of a BigInt (in LIMBS)
 pub const DEFAULT_LIMBS: usize = 100; //= U256::LIMBS;
+
+/// Smallest number of limbs of a big int holding `bits` bits, and at least one
+pub const fn limbs_for_bits(bits: usize) -> usize {
+    if bits == 0 {
+        1
+    } else {
+        bits.div_ceil(Limb::BITS)
+    }
+}
+
+/// Number of limbs of a big int holding the product of primes of `prime_bits` bits, with twice
+/// the bits of the product so that multiplying two residues before reducing them does not wrap
+pub fn limbs_for_primes(prime_bits: &[usize]) -> usize {
+    limbs_for_bits(2 * prime_bits.iter().sum::<usize>())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{limbs_for_bits, limbs_for_primes};
+    use crypto_bigint::Limb;
+
+    #[test]
+    fn limbs_for_bit_sizes() {
+        const LIMBS: usize = limbs_for_bits(256);
+        assert_eq!(LIMBS, 256 / Limb::BITS);
+        assert_eq!(limbs_for_bits(0), 1);
+        assert_eq!(limbs_for_bits(1), 1);
+        assert_eq!(limbs_for_bits(Limb::BITS), 1);
+        assert_eq!(limbs_for_bits(Limb::BITS + 1), 2);
+        assert_eq!(limbs_for_bits(6400), 6400 / Limb::BITS);
+    }
+
+    #[test]
+    fn limbs_for_prime_sizes() {
+        // five 31-bit primes: 155 bits, doubled to 310
+        assert_eq!(limbs_for_primes(&[31; 5]), 310_usize.div_ceil(Limb::BITS));
+        assert_eq!(limbs_for_primes(&[64, 64]), 256 / Limb::BITS);
+        assert_eq!(limbs_for_primes(&[]), 1);
+    }
+}
@@ -0,0 +1,130 @@
+use super::{
+    hensel_code::{chinese_remainder, HenselCode},
+    rational::Rational,
+};
+use crate::bigint::{are_pairwise_coprime, BigIntTrait};
+
+/// Named plaintext slots packed into a single HenselCode by the chinese remainder theorem: each
+/// label is associated with a prime channel, and its rational is encoded modulo that prime.
+/// This is plaintext-only: `pack` and `unpack` never encrypt nor decrypt, and the packed code is
+/// not a ciphertext of any key.
+#[derive(Clone, Debug)]
+pub struct SlotMap<T: BigIntTrait> {
+    slots: Vec<(String, T, Rational<T>)>,
+}
+
+impl<T: BigIntTrait> SlotMap<T> {
+    /// Creates a SlotMap with one zero-valued slot per `(label, prime)` channel. Panics if two
+    /// channels share a label or if the primes are not pairwise distinct.
+    pub fn new(channels: Vec<(&str, T)>) -> Self {
+        for (i, (label, _)) in channels.iter().enumerate() {
+            if channels[..i].iter().any(|(l, _)| l == label) {
+                panic!("two slots are labelled '{}'", label);
+            }
+        }
+        let primes: Vec<T> = channels.iter().map(|(_, p)| p.clone()).collect();
+        if !are_pairwise_coprime(&primes) {
+            panic!("the primes of the slots are not pairwise distinct");
+        }
+        SlotMap {
+            slots: channels
+                .into_iter()
+                .map(|(label, p)| (label.to_string(), p, Rational::default()))
+                .collect(),
+        }
+    }
+
+    /// Sets the value of the slot labelled `label`, returning its previous value, or `None` if
+    /// no slot is labelled `label`
+    pub fn set(&mut self, label: &str, r: Rational<T>) -> Option<Rational<T>> {
+        self.slots
+            .iter_mut()
+            .find(|(l, _, _)| l == label)
+            .map(|(_, _, slot)| std::mem::replace(slot, r))
+    }
+
+    /// Returns the value of the slot labelled `label`
+    pub fn get(&self, label: &str) -> Option<&Rational<T>> {
+        self.slots
+            .iter()
+            .find(|(l, _, _)| l == label)
+            .map(|(_, _, r)| r)
+    }
+
+    /// Packs all the slots into a HenselCode modulo the product of the channel primes
+    pub fn pack(&self) -> HenselCode<T> {
+        let mut codes = self.slots.iter().map(|(_, p, r)| HenselCode::from((p, r)));
+        let first = match codes.next() {
+            Some(hc) => hc,
+            None => panic!("cannot pack an empty slot map"),
+        };
+        codes.fold(first, chinese_remainder)
+    }
+
+    /// Unpacks a HenselCode produced by `pack` (or by homomorphic operations on packed codes)
+    /// into the slots of this SlotMap
+    pub fn unpack(&mut self, hc: &HenselCode<T>) {
+        for (_, p, r) in self.slots.iter_mut() {
+            *r = Rational::from(&hc.change_modulus(p));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SlotMap;
+    use crate::bigint::{BigIntTrait, WrappingCryptoBigInt};
+    use crate::rational::Rational;
+
+    type T = WrappingCryptoBigInt;
+
+    fn rational(num: u128, denom: u128) -> Rational<T> {
+        Rational::<T> {
+            num: T::from_u128(num),
+            denom: T::from_u128(denom),
+        }
+    }
+
+    #[test]
+    fn named_slots() {
+        let channels = vec![("price", T::from_u128(7919)), ("rate", T::from_u128(5897))];
+        let mut slots = SlotMap::new(channels.clone());
+        assert_eq!(
+            slots.set("price", rational(20, 1)),
+            Some(Rational::default())
+        );
+        assert_eq!(slots.set("rate", rational(3, 4)), Some(Rational::default()));
+        assert_eq!(slots.set("volume", rational(1, 1)), None);
+        let packed = slots.pack();
+        assert_eq!(packed.modulus, T::from_u128(7919 * 5897));
+
+        let mut unpacked = SlotMap::new(channels);
+        unpacked.unpack(&packed);
+        assert_eq!(unpacked.get("price"), Some(&rational(20, 1)));
+        assert_eq!(unpacked.get("rate"), Some(&rational(3, 4)));
+        assert_eq!(unpacked.get("volume"), None);
+
+        // operations on packed codes act slot-wise
+        unpacked.unpack(&(&packed + &packed));
+        assert_eq!(unpacked.get("price"), Some(&rational(40, 1)));
+        assert_eq!(unpacked.get("rate"), Some(&rational(3, 2)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn duplicate_label() {
+        SlotMap::new(vec![
+            ("price", T::from_u128(7919)),
+            ("price", T::from_u128(5897)),
+        ]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn duplicate_prime() {
+        SlotMap::new(vec![
+            ("price", T::from_u128(7919)),
+            ("rate", T::from_u128(7919)),
+        ]);
+    }
+}